@@ -0,0 +1,58 @@
+use crate::{
+    container::{container::Container, traits::GetUnchecked},
+    fundemental::index::Index,
+};
+
+use std::cmp::Ordering;
+
+/// Returns the indices of `v`, ordered such that the elements they point to
+/// are sorted according to `cmp`. The order of equal elements is unspecified.
+pub fn argsort_by<'id, C, F>(v: &Container<'id, C>, mut cmp: F) -> Vec<Index<'id>>
+where
+    C: GetUnchecked,
+    F: FnMut(&C::Item, &C::Item) -> Ordering,
+{
+    let mut indices = v.range().into_iter().collect::<Vec<_>>();
+    indices.sort_unstable_by(|&a, &b| cmp(&v[a], &v[b]));
+    indices
+}
+
+/// Returns the indices of `v`, ordered such that the elements they point to
+/// are sorted according to `cmp`. Equal elements keep their original order.
+pub fn stable_argsort_by<'id, C, F>(v: &Container<'id, C>, mut cmp: F) -> Vec<Index<'id>>
+where
+    C: GetUnchecked,
+    F: FnMut(&C::Item, &C::Item) -> Ordering,
+{
+    let mut indices = v.range().into_iter().collect::<Vec<_>>();
+    indices.sort_by(|&a, &b| cmp(&v[a], &v[b]));
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::{scope, scope_same_len};
+
+    #[test]
+    fn test_argsort() {
+        let ages = [31, 25, 40, 25];
+        let names = ["ann", "bob", "cid", "dan"];
+
+        let sorted = scope_same_len(&ages[..], &names[..], |ages, names, proof| {
+            stable_argsort_by(&ages, Ord::cmp)
+                .into_iter()
+                .map(|i| names[proof.index(i)])
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(sorted.unwrap(), ["bob", "dan", "ann", "cid"]);
+
+        let sorted = scope(&ages[..], |ages| {
+            argsort_by(&ages, |a, b| b.cmp(a))
+                .into_iter()
+                .map(|i| ages[i])
+                .collect::<Vec<_>>()
+        });
+        assert_eq!(sorted, [40, 31, 25, 25]);
+    }
+}
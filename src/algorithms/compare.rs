@@ -0,0 +1,194 @@
+use crate::{
+    container::{container::Container, traits::GetUnchecked, ziprange::ZipRange},
+    fundemental::{index::Index, range::Range},
+};
+
+use std::cmp::Ordering;
+
+/// Returns the first pair of indices at which `a` and `b` differ,
+/// according to `eq`.
+///
+/// Only the common prefix of both containers is compared,
+/// so None is returned if one container is a prefix of the other.
+pub fn mismatch_by<'a, 'b, A, B, F>(
+    a: &Container<'a, A>,
+    b: &Container<'b, B>,
+    mut eq: F,
+) -> Option<(Index<'a>, Index<'b>)>
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    F: FnMut(&A::Item, &B::Item) -> bool,
+{
+    a.zipped(b).find(|&(i, j)| !eq(&a[i], &b[j]))
+}
+
+/// Returns the first pair of indices at which `a` and `b` differ.
+///
+/// Only the common prefix of both containers is compared,
+/// so None is returned if one container is a prefix of the other.
+pub fn mismatch<'a, 'b, A, B>(
+    a: &Container<'a, A>,
+    b: &Container<'b, B>,
+) -> Option<(Index<'a>, Index<'b>)>
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    A::Item: PartialEq<B::Item>,
+{
+    mismatch_by(a, b, |x, y| x == y)
+}
+
+/// Returns `true` if `a` and `b` have the same length,
+/// and all their elements are equal according to `eq`.
+pub fn equal_by<'a, 'b, A, B, F>(a: &Container<'a, A>, b: &Container<'b, B>, eq: F) -> bool
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    F: FnMut(&A::Item, &B::Item) -> bool,
+{
+    a.len() == b.len() && mismatch_by(a, b, eq).is_none()
+}
+
+/// Returns `true` if `a` and `b` have the same length,
+/// and all their elements are equal.
+pub fn equal<'a, 'b, A, B>(a: &Container<'a, A>, b: &Container<'b, B>) -> bool
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    A::Item: PartialEq<B::Item>,
+{
+    equal_by(a, b, |x, y| x == y)
+}
+
+/// If `a` starts with the elements of `b`,
+/// returns the matching ranges of `a` and `b`.
+pub fn starts_with<'a, 'b, A, B>(
+    a: &Container<'a, A>,
+    b: &Container<'b, B>,
+) -> Option<(Range<'a>, Range<'b>)>
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    A::Item: PartialEq<B::Item>,
+{
+    matching(a, b, a.zipped(b))
+}
+
+/// If `a` ends with the elements of `b`,
+/// returns the matching ranges of `a` and `b`.
+pub fn ends_with<'a, 'b, A, B>(
+    a: &Container<'a, A>,
+    b: &Container<'b, B>,
+) -> Option<(Range<'a>, Range<'b>)>
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    A::Item: PartialEq<B::Item>,
+{
+    matching(a, b, a.zipped_back(b))
+}
+
+/// Returns the ranges of `zipped` if they cover all of `b`,
+/// and all zipped elements are equal.
+fn matching<'a, 'b, A, B>(
+    a: &Container<'a, A>,
+    b: &Container<'b, B>,
+    zipped: ZipRange<'a, 'b>,
+) -> Option<(Range<'a>, Range<'b>)>
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    A::Item: PartialEq<B::Item>,
+{
+    if zipped.len() < b.len() || !zipped.into_iter().all(|(i, j)| a[i] == b[j]) {
+        return None;
+    }
+
+    Some(zipped.into_ranges())
+}
+
+/// Compares `a` and `b` lexicographically, using `cmp` to compare elements.
+pub fn lexicographic_cmp_by<'a, 'b, A, B, F>(
+    a: &Container<'a, A>,
+    b: &Container<'b, B>,
+    mut cmp: F,
+) -> Ordering
+where
+    A: GetUnchecked,
+    B: GetUnchecked,
+    F: FnMut(&A::Item, &B::Item) -> Ordering,
+{
+    for (i, j) in a.zipped(b) {
+        match cmp(&a[i], &b[j]) {
+            Ordering::Equal => continue,
+            non_eq => return non_eq,
+        }
+    }
+
+    a.len().cmp(&b.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_mismatch() {
+        scope(&[1, 2, 3, 4][..], |a| {
+            scope(&[1, 2, 5][..], |b| {
+                let (i, j) = mismatch(&a, &b).unwrap();
+                assert_eq!((a[i], b[j]), (3, 5));
+                assert!(!equal(&a, &b));
+            });
+
+            scope(&[1, 2][..], |b| {
+                assert!(mismatch(&a, &b).is_none());
+                assert!(!equal(&a, &b));
+            });
+        });
+    }
+
+    #[test]
+    fn test_starts_ends_with() {
+        scope(&[1, 2, 3, 4][..], |a| {
+            scope(&[1, 2][..], |b| {
+                let (ra, rb) = starts_with(&a, &b).unwrap();
+                assert_eq!((&a[ra], &b[rb]), (&[1, 2][..], &[1, 2][..]));
+                assert!(ends_with(&a, &b).is_none());
+            });
+
+            scope(&[3, 4][..], |b| {
+                assert!(starts_with(&a, &b).is_none());
+                let (ra, rb) = ends_with(&a, &b).unwrap();
+                assert_eq!((ra.start(), rb.start()), (2, 0));
+                assert_eq!(a[ra], [3, 4]);
+            });
+
+            scope(&[0, 1, 2, 3, 4][..], |b| {
+                assert!(starts_with(&a, &b).is_none());
+                assert!(ends_with(&a, &b).is_none());
+            });
+        });
+    }
+
+    #[test]
+    fn test_lexicographic_cmp() {
+        scope(&[1, 2, 3][..], |a| {
+            scope(&[1, 2][..], |b| {
+                assert_eq!(lexicographic_cmp_by(&a, &b, Ord::cmp), Ordering::Greater);
+                assert_eq!(lexicographic_cmp_by(&b, &a, Ord::cmp), Ordering::Less);
+            });
+
+            scope(&[1, 3][..], |b| {
+                assert_eq!(lexicographic_cmp_by(&a, &b, Ord::cmp), Ordering::Less);
+            });
+
+            scope(vec![1, 2, 3], |b| {
+                assert_eq!(lexicographic_cmp_by(&a, &b, Ord::cmp), Ordering::Equal);
+                assert!(equal_by(&a, &b, |x, y| x == y));
+            });
+        });
+    }
+}
@@ -1,16 +1,89 @@
-use crate::container::container::scope;
-
-pub fn copy<T: Copy>(src: &[T], dst: &mut [T]) {
-    scope(src, |src| {
-        scope(dst, |mut dst| {
-            for (src_idx, dst_idx) in src.zipped(&dst) {
-                dst[dst_idx] = src[src_idx];
-                // dst[src_idx] = src[dst_idx]; <-- fails to compile, the indices are swapped, and dont belong to the container!!
-            }
-        })
+use crate::{
+    container::{
+        container::{scope, scope2, Container},
+        traits::ContiguousMut,
+    },
+    fundemental::{index::Edge, range::Range},
+};
+
+use std::{cmp::min, ptr};
+
+/// Copies as many elements from `src` into `dst` as fit.
+/// Returns the number of elements copied.
+pub fn copy<T: Copy>(src: &[T], dst: &mut [T]) -> usize {
+    scope2(src, dst, |src, mut dst| {
+        let (src_range, dst_range) = src.zipped(&dst).into_ranges();
+        let len = src_range.len();
+
+        // `src` and `dst` are two distinct borrows, so they can not overlap.
+        unsafe {
+            ptr::copy_nonoverlapping(src[src_range].as_ptr(), dst[dst_range].as_mut_ptr(), len);
+        }
+        len
     })
 }
 
+/// Clones as many elements from `src` into `dst` as fit.
+/// Returns the number of elements cloned.
+pub fn clone_into<T: Clone>(src: &[T], dst: &mut [T]) -> usize {
+    scope2(src, dst, |src, mut dst| {
+        let zipped = src.zipped(&dst);
+        let len = zipped.len();
+
+        for (src_idx, dst_idx) in zipped {
+            dst[dst_idx].clone_from(&src[src_idx]);
+            // dst[src_idx] = src[dst_idx]; <-- fails to compile, the indices are swapped, and dont belong to the container!!
+        }
+        len
+    })
+}
+
+/// Fills `dst` with clones of `value`.
+/// Returns the number of elements written.
+pub fn fill<T: Clone>(dst: &mut [T], value: T) -> usize {
+    fill_with(dst, || value.clone())
+}
+
+/// Fills `dst` with values returned by calling `f` repeatedly.
+/// Returns the number of elements written.
+pub fn fill_with<T, F>(dst: &mut [T], mut f: F) -> usize
+where
+    F: FnMut() -> T,
+{
+    scope(dst, |mut dst| {
+        let range = dst.range();
+
+        for idx in range {
+            dst[idx] = f();
+        }
+        range.len()
+    })
+}
+
+/// Copies the elements of `src` to the position starting at `dest`,
+/// within the same container. The ranges may overlap.
+/// If `src` does not fit after `dest`, only the part that fits is copied.
+///
+/// Returns the number of elements copied.
+pub fn copy_within<'id, C, T, P>(
+    v: &mut Container<'id, C>,
+    src: Range<'id, P>,
+    dest: Edge<'id>,
+) -> usize
+where
+    C: ContiguousMut<Item = T>,
+    T: Copy,
+{
+    let (_, tail) = v.split_at(dest);
+    let len = min(src.len(), tail.len());
+
+    unsafe {
+        let base = v[..].as_mut_ptr();
+        ptr::copy(base.add(src.start()), base.add(dest.integer()), len);
+    }
+    len
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -19,8 +92,61 @@ mod tests {
         let src = [1, 2, 3, 4, 5, 6, 7, 8, 9, 10];
         let mut dst = [0; 10];
 
-        copy(&src, &mut dst);
+        assert_eq!(copy(&src, &mut dst), 10);
 
         assert_eq!(dst, [1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
     }
+
+    #[test]
+    fn test_copy_truncates() {
+        let src = [1, 2, 3, 4];
+        let mut dst = [0; 3];
+
+        assert_eq!(copy(&src, &mut dst), 3);
+        assert_eq!(dst, [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_clone_into() {
+        let src = vec![String::from("a"), String::from("b")];
+        let mut dst = vec![String::new(); 3];
+
+        assert_eq!(clone_into(&src, &mut dst), 2);
+        assert_eq!(dst, ["a", "b", ""]);
+    }
+
+    #[test]
+    fn test_fill() {
+        let mut dst = [0; 4];
+        assert_eq!(fill(&mut dst, 7), 4);
+        assert_eq!(dst, [7; 4]);
+
+        let mut n = 0;
+        fill_with(&mut dst, || {
+            n += 1;
+            n
+        });
+        assert_eq!(dst, [1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_copy_within() {
+        let mut v = [1, 2, 3, 4, 5];
+
+        scope(&mut v[..], |mut v| {
+            let (head, tail) = v.split_at(v.range().upper_middle());
+            // copy [1, 2] onto [4, 5]
+            let (_, dest, _) = tail.split_at(1);
+            assert_eq!(copy_within(&mut v, head, dest.first()), 2);
+        });
+        assert_eq!(v, [1, 2, 3, 1, 2]);
+
+        scope(&mut v[..], |mut v| {
+            let range = v.range();
+            let (_, dest, _) = range.split_at(1);
+            // only 4 of the 5 elements fit.
+            assert_eq!(copy_within(&mut v, range, dest.first()), 4);
+        });
+        assert_eq!(v, [1, 1, 2, 3, 1]);
+    }
 }
@@ -1,2 +1,4 @@
+pub mod argsort;
+pub mod compare;
 pub mod copy;
 pub mod qsort;
@@ -0,0 +1,290 @@
+use super::container::Container;
+use super::traits::ContainerTrait;
+use crate::fundemental::{index::Index, range::Range};
+
+use std::fmt::{self, Debug};
+
+const BITS: usize = 64;
+
+/// A compact set of indices of the container branded with `'id`.
+///
+/// The set always spans the whole container,
+/// so members can be inserted, removed and queried without boundschecks.
+pub struct IndexBitSet<'id> {
+    range: Range<'id>,
+    words: Vec<u64>,
+}
+
+impl<'id> IndexBitSet<'id> {
+    /// Creates an empty set for `container`.
+    #[inline]
+    pub fn new<C>(container: &Container<'id, C>) -> IndexBitSet<'id>
+    where
+        C: ContainerTrait,
+    {
+        let range = container.range();
+
+        IndexBitSet {
+            range,
+            words: vec![0; range.len().div_ceil(BITS)],
+        }
+    }
+
+    /// Creates a set for `container`, containing every index of the container.
+    #[inline]
+    pub fn full<C>(container: &Container<'id, C>) -> IndexBitSet<'id>
+    where
+        C: ContainerTrait,
+    {
+        let range = container.range();
+        IndexBitSet::from_range(container, range)
+    }
+
+    /// Creates a set for `container`, containing every index of `r`.
+    #[inline]
+    pub fn from_range<C, P>(container: &Container<'id, C>, r: Range<'id, P>) -> IndexBitSet<'id>
+    where
+        C: ContainerTrait,
+    {
+        let mut set = IndexBitSet::new(container);
+        for index in r {
+            set.insert(index);
+        }
+        set
+    }
+
+    /// Returns a range over all indices the set can hold.
+    #[inline]
+    pub fn range(&self) -> Range<'id> {
+        self.range
+    }
+
+    /// Inserts `index` into the set.
+    /// Returns `true` if it was not present before.
+    #[inline]
+    pub fn insert(&mut self, index: Index<'id>) -> bool {
+        let (word, mask) = Self::locate(index);
+        let w = unsafe { self.words.get_unchecked_mut(word) };
+        let absent = *w & mask == 0;
+        *w |= mask;
+        absent
+    }
+
+    /// Removes `index` from the set.
+    /// Returns `true` if it was present before.
+    #[inline]
+    pub fn remove(&mut self, index: Index<'id>) -> bool {
+        let (word, mask) = Self::locate(index);
+        let w = unsafe { self.words.get_unchecked_mut(word) };
+        let present = *w & mask != 0;
+        *w &= !mask;
+        present
+    }
+
+    /// Returns `true` if `index` is in the set.
+    #[inline]
+    pub fn contains(&self, index: Index<'id>) -> bool {
+        let (word, mask) = Self::locate(index);
+        unsafe { *self.words.get_unchecked(word) & mask != 0 }
+    }
+
+    /// Returns the number of indices in the set.
+    #[inline]
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    /// Returns `true` if the set holds no indices.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    /// Removes all indices from the set.
+    #[inline]
+    pub fn clear(&mut self) {
+        self.words.iter_mut().for_each(|w| *w = 0);
+    }
+
+    /// Adds all indices of `other` to `self`.
+    #[inline]
+    pub fn union_with(&mut self, other: &IndexBitSet<'id>) {
+        self.combine(other, |a, b| a | b)
+    }
+
+    /// Removes all indices from `self` that are not in `other`.
+    #[inline]
+    pub fn intersect_with(&mut self, other: &IndexBitSet<'id>) {
+        self.combine(other, |a, b| a & b)
+    }
+
+    /// Removes all indices of `other` from `self`.
+    #[inline]
+    pub fn difference_with(&mut self, other: &IndexBitSet<'id>) {
+        self.combine(other, |a, b| a & !b)
+    }
+
+    /// Keeps the indices that are in either `self` or `other`, but not in both.
+    #[inline]
+    pub fn symmetric_difference_with(&mut self, other: &IndexBitSet<'id>) {
+        self.combine(other, |a, b| a ^ b)
+    }
+
+    /// Returns `true` if all indices of `self` are also in `other`.
+    #[inline]
+    pub fn is_subset(&self, other: &IndexBitSet<'id>) -> bool {
+        self.words
+            .iter()
+            .zip(&other.words)
+            .all(|(&a, &b)| a & !b == 0)
+    }
+
+    /// Returns an Iterator over the indices in the set, in ascending order.
+    #[inline]
+    pub fn iter(&self) -> Iter<'_, 'id> {
+        Iter {
+            set: self,
+            word: 0,
+            bits: self.words.first().copied().unwrap_or(0),
+        }
+    }
+
+    #[inline(always)]
+    fn locate(index: Index<'id>) -> (usize, u64) {
+        let i = index.integer();
+        (i / BITS, 1 << (i % BITS))
+    }
+
+    #[inline(always)]
+    fn combine<F>(&mut self, other: &IndexBitSet<'id>, f: F)
+    where
+        F: Fn(u64, u64) -> u64,
+    {
+        // both sets span the same container, so they have the same number of words.
+        for (a, &b) in self.words.iter_mut().zip(&other.words) {
+            *a = f(*a, b);
+        }
+    }
+}
+
+impl<'id> Clone for IndexBitSet<'id> {
+    #[inline]
+    fn clone(&self) -> Self {
+        IndexBitSet {
+            range: self.range,
+            words: self.words.clone(),
+        }
+    }
+}
+
+impl<'id> PartialEq for IndexBitSet<'id> {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.words == other.words
+    }
+}
+
+impl<'id> Eq for IndexBitSet<'id> {}
+
+impl<'id> Debug for IndexBitSet<'id> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(self.iter().map(|i| i.integer()))
+            .finish()
+    }
+}
+
+impl<'a, 'id> IntoIterator for &'a IndexBitSet<'id> {
+    type Item = Index<'id>;
+    type IntoIter = Iter<'a, 'id>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An Iterator over the indices in an `IndexBitSet`.
+pub struct Iter<'a, 'id> {
+    set: &'a IndexBitSet<'id>,
+    word: usize,
+    bits: u64,
+}
+
+impl<'a, 'id> Iterator for Iter<'a, 'id> {
+    type Item = Index<'id>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.bits == 0 {
+            self.word += 1;
+            self.bits = *self.set.words.get(self.word)?;
+        }
+
+        let bit = self.bits.trailing_zeros() as usize;
+        // clear the lowest set bit.
+        self.bits &= self.bits - 1;
+
+        unsafe { Some(Index::new(self.word * BITS + bit)) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_bitset() {
+        scope(vec![0; 130], |v| {
+            let range = v.range().nonempty().unwrap();
+            let (first, last) = (range.first(), range.last());
+
+            let mut set = IndexBitSet::new(&v);
+            assert!(set.insert(first));
+            assert!(set.insert(last));
+            assert!(!set.insert(last));
+            assert!(set.contains(last));
+            assert_eq!(set.count(), 2);
+
+            let members = set.iter().map(|i| i.integer()).collect::<Vec<_>>();
+            assert_eq!(members, [0, 129]);
+
+            assert!(set.remove(first));
+            assert!(!set.contains(first));
+            assert!(set.is_subset(&IndexBitSet::full(&v)));
+        });
+    }
+
+    #[test]
+    fn test_set_algebra() {
+        scope(vec![0; 8], |v| {
+            let (lower, upper) = v.split_at(v.range().upper_middle());
+            let (_, middle, _) = lower.split_at(2);
+            let middle = middle.join(upper.split_at(2).0).unwrap();
+
+            let lower = IndexBitSet::from_range(&v, lower);
+            let middle = IndexBitSet::from_range(&v, middle);
+            let members = |s: &IndexBitSet| s.iter().map(|i| i.integer()).collect::<Vec<_>>();
+
+            let mut set = lower.clone();
+            set.union_with(&middle);
+            assert_eq!(members(&set), [0, 1, 2, 3, 4, 5]);
+
+            let mut set = lower.clone();
+            set.intersect_with(&middle);
+            assert_eq!(members(&set), [2, 3]);
+
+            let mut set = lower.clone();
+            set.difference_with(&middle);
+            assert_eq!(members(&set), [0, 1]);
+
+            let mut set = lower;
+            set.symmetric_difference_with(&middle);
+            assert_eq!(members(&set), [0, 1, 4, 5]);
+
+            set.clear();
+            assert!(set.is_empty());
+        });
+    }
+}
@@ -0,0 +1,52 @@
+/// Brands a container with a fresh, unique lifetime,
+/// without moving the work into a closure like `scope` does.
+///
+/// The branded container lives until the end of the enclosing block,
+/// so it can be used in straight-line code, together with `?` and early returns.
+///
+/// Indices of one brand can not be used with another brand:
+///
+/// ```compile_fail
+/// trusted::brand!(let a = vec![1, 2, 3]);
+/// trusted::brand!(let b = vec![4, 5, 6]);
+///
+/// let first = a.range().nonempty().unwrap().first();
+/// let _ = b[first];
+/// ```
+#[macro_export]
+macro_rules! brand {
+    (let $name:ident = $container:expr $(;)?) => {
+        let container = $container;
+        let branded_place = unsafe { $crate::fundemental::id::Id::unique() };
+        #[allow(unused)]
+        let lifetime_brand = unsafe { $crate::fundemental::id::LifetimeBrand::new(&branded_place) };
+        let $name = unsafe { $crate::container::container::Container::with_id(branded_place, container) };
+    };
+    (let mut $name:ident = $container:expr $(;)?) => {
+        $crate::brand!(let $name = $container);
+        let mut $name = $name;
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::fundemental::index::Index;
+
+    fn last(v: &[i32]) -> Option<i32> {
+        brand!(let v = v);
+        let range = v.range().nonempty()?;
+        Some(v[range.last()])
+    }
+
+    #[test]
+    fn test_brand() {
+        assert_eq!(last(&[1, 2, 3]), Some(3));
+        assert_eq!(last(&[]), None);
+
+        brand!(let mut v = vec![3, 1, 2]);
+        let range = v.range().nonempty().unwrap();
+        let min: Index<'_> = v.min_element_by(range, |a, b| a.cmp(b));
+        v[min] = 0;
+        assert_eq!(v[..], [3, 0, 2]);
+    }
+}
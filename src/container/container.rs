@@ -1,212 +1,1053 @@
+use super::indices::TrustedIndices;
+use super::iter::{IterIndexed, IterIndexedMut};
+use super::rebrand::Rebrand;
+use super::samelen::SameLen;
+use super::split::{Runs, Split, SplitN};
 use super::traits::*;
 use super::ziprange::ZipRange;
 use crate::fundemental::proof::*;
-use crate::fundemental::{id::Id, index::Index, range::Range};
+use crate::fundemental::{
+    compact::{CompactIndex, IndexWidth, Width},
+    id::Id,
+    index::{Edge, Index, IndexError},
+    range::Range,
+    unbranded::{UnbrandedIndex, UnbrandedRange},
+};
+
+use std::{cmp::Ordering, marker::PhantomData};
 
 /// A container is a datastructure wrapped over `C`.
 /// `C` is structure consisting of contiguous memory,
 /// like a slice or vector.
-pub struct Container<'id, C> {
+pub struct Container<'id, C, Mode = Fixed> {
     _id: Id<'id>,
     container: C,
+    mode: PhantomData<Mode>,
+}
+
+/// The default mode of a container, in which its length never changes.
+/// Structures that depend on the length of the container,
+/// like `SameLen` or `SideTable`, can only be created in this mode.
+pub enum Fixed {}
+
+/// The mode of a container that can only grow by appending elements.
+/// Indices stay valid when elements are appended,
+/// but the length of the container is not fixed.
+///
+/// ```compile_fail
+/// use trusted::container::{container::scope_append_only, sidetable::SideTable};
+///
+/// scope_append_only(vec![1, 2, 3], |v| {
+///     let table = SideTable::new(&v, 0);
+/// });
+/// ```
+pub enum AppendOnly {}
+
+impl<'id, C, T, M> Container<'id, C, M>
+where
+    C: ContainerTrait<Item = T>,
+{
+    /// Returns the length of the container.
+    #[inline(always)]
+    pub fn len(&self) -> usize {
+        self.container.base_len()
+    }
+
+    /// Returns `true` if the container is empty.
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a range into the container.
+    #[inline(always)]
+    pub fn range(&self) -> Range<'id> {
+        unsafe { Range::from(0, self.len()) }
+    }
+
+    /// Consumes the container, returning the wrapped `C`.
+    #[inline(always)]
+    pub fn into_inner(self) -> C {
+        self.container
+    }
+
+    /// Calls `f` with the container under a new brand.
+    /// Indices of the current brand can not be used with the new one,
+    /// unless they are unbranded and vetted again with `revet`.
+    #[inline]
+    pub fn rescope<F, Out>(self, f: F) -> Out
+    where
+        F: for<'new> FnOnce(Container<'new, C, M>) -> Out,
+    {
+        f(Container {
+            _id: Id::new(),
+            container: self.container,
+            mode: PhantomData,
+        })
+    }
+
+    /// Returns Some if `index` is in bounds of the container, None otherwise.
+    #[inline]
+    pub fn revet(&self, index: UnbrandedIndex) -> Option<Index<'id>> {
+        if index.integer() < self.len() {
+            unsafe { Some(Index::new(index.integer())) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns Some if `range` is a valid range of the container, None otherwise.
+    #[inline]
+    pub fn revet_range(&self, range: UnbrandedRange) -> Option<Range<'id>> {
+        if range.start() <= range.end() && range.end() <= self.len() {
+            unsafe { Some(Range::from(range.start(), range.end())) }
+        } else {
+            None
+        }
+    }
+
+    /// Zips the range's of `self` and `other`.
+    /// The returning range will be the shortest of the two.
+    #[inline(always)]
+    pub fn zipped<'other, Q, N>(&self, other: &Container<'other, Q, N>) -> ZipRange<'id, 'other>
+    where
+        Q: ContainerTrait,
+    {
+        use std::cmp::min;
+
+        let len = min(self.len(), other.len());
+
+        unsafe { ZipRange::new(0, len) }
+    }
+
+    /// Zips the range's of `self` and `other`, aligned at their ends.
+    /// The returning range will be the shortest of the two,
+    /// covering the last elements of both containers.
+    #[inline(always)]
+    pub fn zipped_back<'other, Q, N>(
+        &self,
+        other: &Container<'other, Q, N>,
+    ) -> ZipRange<'id, 'other>
+    where
+        Q: ContainerTrait,
+    {
+        use std::cmp::min;
+
+        let len = min(self.len(), other.len());
+
+        unsafe { ZipRange::with_offsets(self.len() - len, other.len() - len, len) }
+    }
+
+    /// Validates all of `indices` against the container in one pass.
+    /// Returns an error holding the first index that is out of bounds, if any.
+    #[inline]
+    pub fn vet_all(&self, indices: &[usize]) -> Result<TrustedIndices<'id>, IndexError> {
+        let len = self.len();
+        let max = indices.iter().fold(0, |max, &i| std::cmp::max(max, i));
+
+        if !indices.is_empty() && max >= len {
+            let index = indices.iter().copied().find(|&i| i >= len).unwrap_or(max);
+            return Err(IndexError::new(index, len));
+        }
+
+        unsafe { Ok(TrustedIndices::new(indices)) }
+    }
+
+    /// Clones the elements at `indices` into `out`, in order.
+    /// Stops when either `indices` or `out` is exhausted.
+    ///
+    /// Returns the number of elements written.
+    #[inline]
+    pub fn gather(&self, indices: &TrustedIndices<'id>, out: &mut [T]) -> usize
+    where
+        T: Clone,
+        C: GetUnchecked,
+    {
+        let mut written = 0;
+        for (dst, &i) in out.iter_mut().zip(indices) {
+            dst.clone_from(&self[i]);
+            written += 1;
+        }
+        written
+    }
+
+    /// Clones the elements of `values` to the positions at `indices`, in order.
+    /// Stops when either `indices` or `values` is exhausted.
+    ///
+    /// Returns the number of elements written.
+    #[inline]
+    pub fn scatter(&mut self, indices: &TrustedIndices<'id>, values: &[T]) -> usize
+    where
+        T: Clone,
+        C: GetUncheckedMut,
+    {
+        let mut written = 0;
+        for (src, &i) in values.iter().zip(indices) {
+            self[i].clone_from(src);
+            written += 1;
+        }
+        written
+    }
+
+    /// Split's the container at `index`, returning 2 ranges into the container.
+    /// Proof of length `P` is transferred to the latter range.
+    #[inline(always)]
+    pub fn split_at<P>(&self, index: Index<'id, P>) -> (Range<'id>, Range<'id, P>) {
+        unsafe {
+            (
+                Range::from(0, index.index),
+                Range::from_any(index.index, self.len()),
+            )
+        }
+    }
+
+    /// Swaps element at index `a` with element at index `b`.
+    #[inline(always)]
+    pub fn swap(&mut self, a: Index<'id>, b: Index<'id>)
+    where
+        C: GetUncheckedMut,
+    {
+        use std::ptr;
+
+        // every pointer is used before the next one is taken,
+        // so no reborrow of the container invalidates a pointer that is still in use.
+        unsafe {
+            let tmp = ptr::read(self.container.unchecked_mut(a.integer()));
+            let elt = ptr::read(self.container.unchecked_mut(b.integer()));
+            ptr::write(self.container.unchecked_mut(a.integer()), elt);
+            ptr::write(self.container.unchecked_mut(b.integer()), tmp);
+        }
+    }
+
+    /// Scans the container backwards from `index`,
+    /// continue's scanning as long as the closure returns true.
+    ///
+    /// Returns a range of the scanned indices, including `index`.
+    #[inline]
+    pub fn scan_from_rev<'b, F>(&'b self, index: Index<'id>, mut f: F) -> Range<'id, NonEmpty>
+    where
+        F: FnMut(&'b T) -> bool,
+        T: 'b,
+        C: Contiguous,
+    {
+        unsafe {
+            let mut start = index;
+            for elt in self[..index].iter().rev() {
+                if !f(elt) {
+                    break;
+                }
+                start.index -= 1;
+            }
+            Range::from_ne(start.index, index.index + 1)
+        }
+    }
+
+    /// Scans the container forwards from `index`,
+    /// continue's scanning as long as the closure returns true.
+    ///
+    /// Returns a range of the scanned indices, including `index`.
+    #[inline]
+    pub fn scan_from<'b, F>(&'b self, index: Index<'id>, mut f: F) -> Range<'id, NonEmpty>
+    where
+        F: FnMut(&'b T) -> bool,
+        T: 'b,
+        C: Contiguous,
+    {
+        unsafe {
+            let mut end = index;
+            for elt in self[index..].iter().skip(1) {
+                if !f(elt) {
+                    break;
+                }
+                end.index += 1;
+            }
+            Range::from_ne(index.index, end.index + 1)
+        }
+    }
+
+    /// Scans the container forwards from `index`, handing out mutable references,
+    /// continue's scanning as long as the closure returns true.
+    ///
+    /// Returns a range of the scanned indices, including `index`.
+    #[inline]
+    pub fn scan_from_mut<F>(&mut self, index: Index<'id>, mut f: F) -> Range<'id, NonEmpty>
+    where
+        F: FnMut(&mut T) -> bool,
+        C: ContiguousMut,
+    {
+        unsafe {
+            let mut end = index;
+            for elt in self[index..].iter_mut().skip(1) {
+                if !f(elt) {
+                    break;
+                }
+                end.index += 1;
+            }
+            Range::from_ne(index.index, end.index + 1)
+        }
+    }
+
+    /// Scans the container backwards from `index`, handing out mutable references,
+    /// continue's scanning as long as the closure returns true.
+    ///
+    /// Returns a range of the scanned indices, including `index`.
+    #[inline]
+    pub fn scan_from_rev_mut<F>(&mut self, index: Index<'id>, mut f: F) -> Range<'id, NonEmpty>
+    where
+        F: FnMut(&mut T) -> bool,
+        C: ContiguousMut,
+    {
+        unsafe {
+            let mut start = index;
+            for elt in self[..index].iter_mut().rev() {
+                if !f(elt) {
+                    break;
+                }
+                start.index -= 1;
+            }
+            Range::from_ne(start.index, index.index + 1)
+        }
+    }
+
+    /// Scans forwards from `index` without leaving the range `r`,
+    /// continue's scanning as long as the closure returns true.
+    /// The closure receives the index of each scanned element.
+    ///
+    /// Returns a range of the scanned indices, including `index`,
+    /// or None if `index` is not within `r`.
+    #[inline]
+    pub fn scan_within<'b, P, F>(
+        &'b self,
+        r: Range<'id, P>,
+        index: Index<'id>,
+        mut f: F,
+    ) -> Option<Range<'id, NonEmpty>>
+    where
+        F: FnMut(Index<'id>, &'b T) -> bool,
+        T: 'b,
+        C: GetUnchecked,
+    {
+        if index.index < r.start() || index.index >= r.end() {
+            return None;
+        }
+
+        unsafe {
+            let mut end = index.index + 1;
+            while end < r.end() {
+                let next = Index::new(end);
+                if !f(next, &self[next]) {
+                    break;
+                }
+                end += 1;
+            }
+            Some(Range::from_ne(index.index, end))
+        }
+    }
+
+    /// Scans backwards from `index` without leaving the range `r`,
+    /// continue's scanning as long as the closure returns true.
+    /// The closure receives the index of each scanned element.
+    ///
+    /// Returns a range of the scanned indices, including `index`,
+    /// or None if `index` is not within `r`.
+    #[inline]
+    pub fn scan_within_rev<'b, P, F>(
+        &'b self,
+        r: Range<'id, P>,
+        index: Index<'id>,
+        mut f: F,
+    ) -> Option<Range<'id, NonEmpty>>
+    where
+        F: FnMut(Index<'id>, &'b T) -> bool,
+        T: 'b,
+        C: GetUnchecked,
+    {
+        if index.index < r.start() || index.index >= r.end() {
+            return None;
+        }
+
+        unsafe {
+            let mut start = index.index;
+            while start > r.start() {
+                let next = Index::new(start - 1);
+                if !f(next, &self[next]) {
+                    break;
+                }
+                start -= 1;
+            }
+            Some(Range::from_ne(start, index.index + 1))
+        }
+    }
+
+    #[inline]
+    pub fn rotate1_up(&mut self, r: Range<'id, NonEmpty>)
+    where
+        C: Contiguous + GetUncheckedMut,
+    {
+        use std::{mem, ptr};
+
+        if r.first() != r.last() {
+            unsafe {
+                let last_ptr = &self[r.last()] as *const C::Item;
+                let first_ptr = &mut self[r.first()] as *mut C::Item;
+                let tmp = ptr::read(last_ptr);
+                ptr::copy(first_ptr, first_ptr.offset(1), r.len() - 1);
+                ptr::copy_nonoverlapping(&tmp, first_ptr, 1);
+                mem::forget(tmp);
+            }
+        }
+    }
+
+    /// Returns the index of the first element in `r` for which `pred` returns true.
+    #[inline]
+    pub fn position<P, F>(&self, r: Range<'id, P>, mut pred: F) -> Option<Index<'id>>
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUnchecked,
+    {
+        r.into_iter().find(|&i| pred(&self[i]))
+    }
+
+    /// Returns the index of the last element in `r` for which `pred` returns true.
+    #[inline]
+    pub fn rposition<P, F>(&self, r: Range<'id, P>, mut pred: F) -> Option<Index<'id>>
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUnchecked,
+    {
+        r.into_iter().rev().find(|&i| pred(&self[i]))
+    }
+
+    /// Returns an Iterator over the maximal runs of the container,
+    /// in which `f(prev, next)` holds for each pair of consecutive elements.
+    #[inline]
+    pub fn runs_by<F>(&self, f: F) -> Runs<'_, 'id, C, F, M>
+    where
+        F: FnMut(&T, &T) -> bool,
+        C: GetUnchecked,
+    {
+        Runs::new(self, self.range(), f)
+    }
+
+    /// Returns an Iterator over the ranges of the container,
+    /// separated by elements for which `pred` returns true.
+    #[inline]
+    pub fn split_by<F>(&self, pred: F) -> Split<'_, 'id, C, F, M>
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUnchecked,
+    {
+        Split::new(self, self.range(), pred)
+    }
+
+    /// Like `split_by`, but returns at most `n` ranges.
+    /// The last range contains the remainder of the container.
+    #[inline]
+    pub fn splitn<F>(&self, n: usize, pred: F) -> SplitN<'_, 'id, C, F, M>
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUnchecked,
+    {
+        SplitN::new(self.split_by(pred), n)
+    }
+
+    /// Returns the number of elements in `r` for which `pred` returns true.
+    #[inline]
+    pub fn count_if<P, F>(&self, r: Range<'id, P>, mut pred: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUnchecked,
+    {
+        r.into_iter().filter(|&i| pred(&self[i])).count()
+    }
+
+    /// Returns the index of the minimum element in `r`, according to `cmp`.
+    /// If several elements are equally minimum, the first one is returned.
+    #[inline]
+    pub fn min_element_by<F>(&self, r: Range<'id, NonEmpty>, mut cmp: F) -> Index<'id>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        C: GetUnchecked,
+    {
+        let mut min = r.first();
+        for i in r.tail() {
+            if cmp(&self[i], &self[min]) == Ordering::Less {
+                min = i;
+            }
+        }
+        min
+    }
+
+    /// Returns the index of the maximum element in `r`, according to `cmp`.
+    /// If several elements are equally maximum, the last one is returned.
+    #[inline]
+    pub fn max_element_by<F>(&self, r: Range<'id, NonEmpty>, mut cmp: F) -> Index<'id>
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        C: GetUnchecked,
+    {
+        let mut max = r.first();
+        for i in r.tail() {
+            if cmp(&self[i], &self[max]) != Ordering::Less {
+                max = i;
+            }
+        }
+        max
+    }
+
+    /// Returns the indices of the minimum and maximum element in `r`, according to `cmp`.
+    /// Ties are resolved like `min_element_by` and `max_element_by` do.
+    #[inline]
+    pub fn minmax_element_by<F>(
+        &self,
+        r: Range<'id, NonEmpty>,
+        mut cmp: F,
+    ) -> (Index<'id>, Index<'id>)
+    where
+        F: FnMut(&T, &T) -> Ordering,
+        C: GetUnchecked,
+    {
+        let (mut min, mut max) = (r.first(), r.first());
+        for i in r.tail() {
+            if cmp(&self[i], &self[min]) == Ordering::Less {
+                min = i;
+            }
+            if cmp(&self[i], &self[max]) != Ordering::Less {
+                max = i;
+            }
+        }
+        (min, max)
+    }
+
+    /// Returns the indices of the minimum and maximum element in `r`.
+    #[inline]
+    pub fn minmax_element(&self, r: Range<'id, NonEmpty>) -> (Index<'id>, Index<'id>)
+    where
+        T: Ord,
+        C: GetUnchecked,
+    {
+        self.minmax_element_by(r, Ord::cmp)
+    }
+
+    /// Removes consecutive elements of `r` for which `same(prev, next)` returns true,
+    /// keeping the first element of each such run.
+    /// `prev` is the last kept element, and `next` the element that is checked.
+    ///
+    /// Only elements within `r` are compared and moved:
+    /// the first element of `r` is always kept, even if it equals the element before `r`,
+    /// and elements outside of `r` are left untouched.
+    ///
+    /// The kept elements are moved to the front of `r`, preserving their order.
+    /// Returns the edge between the kept elements and the removed ones,
+    /// as an index of the whole container, somewhere between the start and end of `r`.
+    /// The removed elements are not dropped,
+    /// but left between the returned edge and the end of `r`, in unspecified order.
+    #[inline]
+    pub fn dedup_by<P, F>(&mut self, r: Range<'id, P>, mut same: F) -> Edge<'id>
+    where
+        F: FnMut(&T, &T) -> bool,
+        C: GetUncheckedMut,
+    {
+        let r = match r.nonempty() {
+            Some(r) => r,
+            None => return r.first().no_proof(),
+        };
+
+        unsafe {
+            let mut write = r.first();
+            for read in r.tail() {
+                if !same(&self[write], &self[read]) {
+                    write.index += 1;
+                    self.swap(write, read);
+                }
+            }
+            Index::new(write.index + 1)
+        }
+    }
+
+    /// Moves all elements for which `pred` returns true to the front of the container,
+    /// preserving their order.
+    ///
+    /// Returns the edge between the kept elements and the others,
+    /// which are left at the back of the container in unspecified order.
+    #[inline]
+    pub fn retain_in_place<F>(&mut self, mut pred: F) -> Edge<'id>
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUncheckedMut,
+    {
+        unsafe {
+            let mut write: Index<'id> = Index::new(0);
+            for read in self.range() {
+                if pred(&self[read]) {
+                    self.swap(write, read);
+                    write.index += 1;
+                }
+            }
+            Index::new(write.index)
+        }
+    }
+
+    /// Reorders the container such that all elements for which `pred` returns true
+    /// precede those for which it returns false. The order within both groups is unspecified.
+    ///
+    /// Returns the edge between both groups.
+    #[inline]
+    pub fn partition_in_place<F>(&mut self, mut pred: F) -> Edge<'id>
+    where
+        F: FnMut(&T) -> bool,
+        C: GetUncheckedMut,
+    {
+        let mut range = self.range();
+
+        loop {
+            while let Some(r) = range.nonempty() {
+                if !pred(&self[r.first()]) {
+                    break;
+                }
+                range = r.tail();
+            }
+
+            while let Some(r) = range.nonempty() {
+                if pred(&self[r.last()]) {
+                    break;
+                }
+                range = r.head();
+            }
+
+            match range.nonempty() {
+                // `first` fails `pred`, and `last` passes it,
+                // so the range is at least 2 long.
+                Some(r) => {
+                    self.swap(r.first(), r.last());
+                    range = unsafe { Range::from(r.start() + 1, r.end() - 1) };
+                }
+                None => return range.first(),
+            }
+        }
+    }
+
+    /// Reorders the container such that all elements for which `pred` returns true
+    /// precede those for which it returns false, preserving the order within both groups.
+    ///
+    /// Returns the edge between both groups.
+    #[inline]
+    pub fn stable_partition<F>(&mut self, mut pred: F) -> Edge<'id>
+    where
+        F: FnMut(&T) -> bool,
+        C: ContiguousMut + GetUncheckedMut,
+    {
+        let range = self.range();
+        self.stable_partition_range(range, &mut pred)
+    }
+
+    fn stable_partition_range<P, F>(&mut self, r: Range<'id, P>, pred: &mut F) -> Edge<'id>
+    where
+        P: LengthProof,
+        F: FnMut(&T) -> bool,
+        C: ContiguousMut + GetUncheckedMut,
+    {
+        if r.len() <= 1 {
+            let keep = r.nonempty().is_some_and(|r| pred(&self[r.first()]));
+            return unsafe { Index::new(r.start() + keep as usize) };
+        }
+
+        // partition both halves, then rotate the failing part of the lower half
+        // behind the passing part of the upper half.
+        let (lower, upper) = r.split_in_half();
+        let lower_edge = self.stable_partition_range(lower, pred);
+        let upper_edge = self.stable_partition_range(upper, pred);
+
+        unsafe {
+            let moved: Range<'id> = Range::from(lower_edge.integer(), upper_edge.integer());
+            self[moved].rotate_left(upper.start() - lower_edge.integer());
+            Index::new(lower_edge.integer() + upper_edge.integer() - upper.start())
+        }
+    }
+
+    /// Returns an Iterator over the elements of the container, together with their indices.
+    #[inline]
+    pub fn iter_indexed(&self) -> IterIndexed<'_, 'id, T>
+    where
+        C: Contiguous,
+    {
+        self.iter_indexed_within(self.range())
+    }
+
+    /// Returns an Iterator over the elements of `r`, together with their indices.
+    #[inline]
+    pub fn iter_indexed_within<P>(&self, r: Range<'id, P>) -> IterIndexed<'_, 'id, T>
+    where
+        C: Contiguous,
+    {
+        unsafe { IterIndexed::new(self.container.begin(), r.start(), r.end()) }
+    }
+
+    /// Returns an Iterator over mutable references to the elements of the container,
+    /// together with their indices.
+    #[inline]
+    pub fn iter_indexed_mut(&mut self) -> IterIndexedMut<'_, 'id, T>
+    where
+        C: ContiguousMut,
+    {
+        let range = self.range();
+        self.iter_indexed_mut_within(range)
+    }
+
+    /// Returns an Iterator over mutable references to the elements of `r`,
+    /// together with their indices.
+    #[inline]
+    pub fn iter_indexed_mut_within<P>(&mut self, r: Range<'id, P>) -> IterIndexedMut<'_, 'id, T>
+    where
+        C: ContiguousMut,
+    {
+        let base = self.container.as_mut_slice().as_mut_ptr();
+        unsafe { IterIndexedMut::new(base, r.start(), r.end()) }
+    }
+
+    /// Returns the elements of `r` as two slices,
+    /// for containers that store their elements in two parts.
+    /// The second slice is empty if `r` lies within one part.
+    #[inline]
+    pub fn as_slices<P>(&self, r: Range<'id, P>) -> (&[T], &[T])
+    where
+        C: AsSlices,
+    {
+        let (front, back) = self.container.as_slices();
+        let mid = front.len();
+
+        unsafe {
+            if r.end() <= mid {
+                (front.get_unchecked(r.start()..r.end()), &[])
+            } else if r.start() >= mid {
+                (back.get_unchecked(r.start() - mid..r.end() - mid), &[])
+            } else {
+                (
+                    front.get_unchecked(r.start()..),
+                    back.get_unchecked(..r.end() - mid),
+                )
+            }
+        }
+    }
+
+    /// Returns the elements of `r` as two mutable slices,
+    /// for containers that store their elements in two parts.
+    /// The second slice is empty if `r` lies within one part.
+    #[inline]
+    pub fn as_mut_slices<P>(&mut self, r: Range<'id, P>) -> (&mut [T], &mut [T])
+    where
+        C: AsSlicesMut,
+    {
+        let (front, back) = self.container.as_mut_slices();
+        let mid = front.len();
+
+        unsafe {
+            if r.end() <= mid {
+                (front.get_unchecked_mut(r.start()..r.end()), &mut [])
+            } else if r.start() >= mid {
+                (
+                    back.get_unchecked_mut(r.start() - mid..r.end() - mid),
+                    &mut [],
+                )
+            } else {
+                (
+                    front.get_unchecked_mut(r.start()..),
+                    back.get_unchecked_mut(..r.end() - mid),
+                )
+            }
+        }
+    }
+
+    /// Returns the index of the element `elt` refers to,
+    /// or None if `elt` does not point into the container.
+    ///
+    /// Elements of zero-sized types all live at the same address,
+    /// so their index can not be recovered, and None is returned.
+    #[inline]
+    pub fn index_of_ref(&self, elt: &T) -> Option<Index<'id>>
+    where
+        C: Contiguous,
+    {
+        let range = self.range_of_subslice(std::slice::from_ref(elt))?;
+        range.nonempty().map(|r| r.first())
+    }
+
+    /// Returns the range of the container `slice` refers to,
+    /// or None if `slice` does not lie within the container.
+    ///
+    /// Elements of zero-sized types all live at the same address,
+    /// so their range can not be recovered, and None is returned.
+    #[inline]
+    pub fn range_of_subslice(&self, slice: &[T]) -> Option<Range<'id>>
+    where
+        C: Contiguous,
+    {
+        let size = std::mem::size_of::<T>();
+        if size == 0 {
+            return None;
+        }
+
+        let begin = self.container.begin() as usize;
+        let end = self.container.end() as usize;
+        let start = slice.as_ptr() as usize;
+
+        if start < begin || start > end || (start - begin) % size != 0 {
+            return None;
+        }
+
+        let first = (start - begin) / size;
+        if slice.len() > self.len() - first {
+            return None;
+        }
+
+        unsafe { Some(Range::from(first, first + slice.len())) }
+    }
 }
 
 impl<'id, C, T> Container<'id, C>
 where
     C: ContainerTrait<Item = T>,
 {
-    /// Returns the length of the container.
-    #[inline(always)]
-    pub fn len(&self) -> usize {
-        self.container.base_len()
+    /// Returns a proof that `self` and `other` have the same length,
+    /// or None if their lengths differ.
+    #[inline]
+    pub fn same_len<'other, Q>(&self, other: &Container<'other, Q>) -> Option<SameLen<'id, 'other>>
+    where
+        Q: ContainerTrait,
+    {
+        if self.len() == other.len() {
+            unsafe { Some(SameLen::new(self.len())) }
+        } else {
+            None
+        }
     }
 
-    /// Returns a range into the container.
-    #[inline(always)]
-    pub fn range(&self) -> Range<'id> {
-        unsafe { Range::from(0, self.len()) }
+    /// Returns a proof that all indices of the container fit in `W`,
+    /// or None if the container is too long.
+    #[inline]
+    pub fn width<W: IndexWidth>(&self) -> Option<Width<'id, W>> {
+        if self.len() <= W::MAX {
+            unsafe { Some(Width::new()) }
+        } else {
+            None
+        }
     }
+}
 
-    /// Zips the range's of `self` and `other`.
-    /// The returning range will be the shortest of the two.
-    #[inline(always)]
-    pub fn zipped<'other, Q>(&self, other: &Container<'other, Q>) -> ZipRange<'id, 'other>
+impl<'id, T, M> Container<'id, Vec<T>, M> {
+    /// Shortens the container to `len` elements,
+    /// and calls `f` with the shortened container under a new brand.
+    /// The `Rebrand` map translates surviving indices into the new brand.
+    #[inline]
+    pub fn truncate<F, Out>(mut self, len: Edge<'id>, f: F) -> Out
     where
-        Q: ContainerTrait,
+        F: for<'new> FnOnce(Container<'new, Vec<T>, M>, Rebrand<'id, 'new>) -> Out,
     {
-        use std::cmp::min;
+        let removed = self.len() - len.integer();
+        self.container.truncate(len.integer());
+        unsafe { self.rebrand(len.integer(), removed, f) }
+    }
 
-        let len = min(self.len(), other.len());
+    /// Removes all elements of the container,
+    /// and calls `f` with the empty container under a new brand.
+    #[inline]
+    pub fn clear<F, Out>(mut self, f: F) -> Out
+    where
+        F: for<'new> FnOnce(Container<'new, Vec<T>, M>, Rebrand<'id, 'new>) -> Out,
+    {
+        let removed = self.len();
+        self.container.clear();
+        unsafe { self.rebrand(0, removed, f) }
+    }
 
-        unsafe { ZipRange::new(0, len) }
+    /// Removes the last element of the container,
+    /// and calls `f` with the shortened container under a new brand,
+    /// together with the removed element, or None if the container was empty.
+    #[inline]
+    pub fn pop<F, Out>(mut self, f: F) -> Out
+    where
+        F: for<'new> FnOnce(Container<'new, Vec<T>, M>, Rebrand<'id, 'new>, Option<T>) -> Out,
+    {
+        let elt = self.container.pop();
+        let removed = elt.is_some() as usize;
+        let cut = self.len();
+        unsafe { self.rebrand(cut, removed, |v, map| f(v, map, elt)) }
     }
 
-    /// Split's the container at `index`, returning 2 ranges into the container.
-    /// Proof of length `P` is transferred to the latter range.
-    #[inline(always)]
-    pub fn split_at<P>(&self, index: Index<'id, P>) -> (Range<'id>, Range<'id, P>) {
-        unsafe {
-            (
-                Range::from(0, index.index),
-                Range::from_any(index.index, self.len()),
-            )
-        }
+    /// Removes the element at `index`, shifting all elements after it down,
+    /// and calls `f` with the shortened container under a new brand,
+    /// together with the removed element.
+    #[inline]
+    pub fn remove<F, Out>(mut self, index: Index<'id>, f: F) -> Out
+    where
+        F: for<'new> FnOnce(Container<'new, Vec<T>, M>, Rebrand<'id, 'new>, T) -> Out,
+    {
+        let elt = self.container.remove(index.integer());
+        unsafe { self.rebrand(index.integer(), 1, |v, map| f(v, map, elt)) }
     }
 
-    /// Swaps element at index `a` with element at index `b`.
+    /// Calls `f` with the container under a new brand.
+    /// This function is marked unsafe,
+    /// because it can not be proved `removed` elements were taken out at `cut`.
     #[inline(always)]
-    pub fn swap(&mut self, a: Index<'id>, b: Index<'id>)
+    unsafe fn rebrand<F, Out>(self, cut: usize, removed: usize, f: F) -> Out
     where
-        C: GetUncheckedMut,
+        F: for<'new> FnOnce(Container<'new, Vec<T>, M>, Rebrand<'id, 'new>) -> Out,
     {
-        use std::ptr;
-
-        unsafe {
-            let self_mut = self as *mut Self;
-            let pa: *mut _ = &mut (*self_mut)[a];
-            let pb: *mut _ = &mut (*self_mut)[b];
-
-            ptr::swap(pa, pb);
-        }
+        f(
+            Container {
+                _id: Id::new(),
+                container: self.container,
+                mode: PhantomData,
+            },
+            Rebrand::new(cut, removed),
+        )
     }
+}
 
-    /// Scans the container backwards from `index`,
-    /// continue's scanning as long as the closure returns true.
-    ///
-    /// Returns a range of the scanned indices, including `index`.
+impl<'id, T> Container<'id, Vec<T>, AppendOnly> {
+    /// Appends `elt` to the back of the container, returning its index.
+    /// Existing indices stay valid. Call `range` again to get the new full range.
     #[inline]
-    pub fn scan_from_rev<'b, F>(&'b self, index: Index<'id>, mut f: F) -> Range<'id, NonEmpty>
-    where
-        F: FnMut(&'b T) -> bool,
-        T: 'b,
-        C: Contiguous,
-    {
-        unsafe {
-            let mut start = index;
-            for elt in self[..index].iter().rev() {
-                if !f(elt) {
-                    break;
-                }
-                start.index -= 1;
-            }
-            Range::from_ne(start.index, index.index + 1)
-        }
+    pub fn push(&mut self, elt: T) -> Index<'id> {
+        let index = self.len();
+        self.container.push(elt);
+        unsafe { Index::new(index) }
     }
 
+    /// Appends all elements of `iter` to the back of the container,
+    /// returning the range of the appended elements.
+    /// Existing indices stay valid. Call `range` again to get the new full range.
     #[inline]
-    pub fn rotate1_up(&mut self, r: Range<'id, NonEmpty>)
+    pub fn extend<I>(&mut self, iter: I) -> Range<'id>
     where
-        C: Contiguous + GetUncheckedMut,
+        I: IntoIterator<Item = T>,
     {
-        use std::{mem, ptr};
+        let start = self.len();
+        self.container.extend(iter);
+        unsafe { Range::from(start, self.len()) }
+    }
 
-        if r.first() != r.last() {
-            unsafe {
-                let last_ptr = &self[r.last()] as *const C::Item;
-                let first_ptr = &mut self[r.first()] as *mut C::Item;
-                let tmp = ptr::read(last_ptr);
-                ptr::copy(first_ptr, first_ptr.offset(1), r.len() - 1);
-                ptr::copy_nonoverlapping(&tmp, first_ptr, 1);
-                mem::forget(tmp);
-            }
+    /// Fixes the length of the container,
+    /// allowing structures that depend on the length to be created.
+    #[inline]
+    pub fn freeze(self) -> Container<'id, Vec<T>> {
+        Container {
+            _id: self._id,
+            container: self.container,
+            mode: PhantomData,
         }
     }
 }
 
 use std::ops;
 
-impl<'id, C> ops::Index<Index<'id>> for Container<'id, C>
+impl<'id, C, Tag, M> ops::Index<Index<'id, NonEmpty, Tag>> for Container<'id, C, M>
 where
     C: GetUnchecked,
 {
     type Output = C::Item;
 
     #[inline(always)]
-    fn index(&self, index: Index<'id>) -> &Self::Output {
+    fn index(&self, index: Index<'id, NonEmpty, Tag>) -> &Self::Output {
         unsafe { self.container.unchecked(index.index) }
     }
 }
 
-impl<'id, C> ops::IndexMut<Index<'id>> for Container<'id, C>
+impl<'id, C, Tag, M> ops::IndexMut<Index<'id, NonEmpty, Tag>> for Container<'id, C, M>
 where
     C: GetUncheckedMut,
 {
     #[inline(always)]
-    fn index_mut(&mut self, index: Index<'id>) -> &mut Self::Output {
+    fn index_mut(&mut self, index: Index<'id, NonEmpty, Tag>) -> &mut Self::Output {
         unsafe { self.container.unchecked_mut(index.index) }
     }
 }
 
-impl<'id, T, C, P> ops::Index<Range<'id, P>> for Container<'id, C>
+impl<'id, C, W, Tag, M> ops::Index<CompactIndex<'id, W, NonEmpty, Tag>> for Container<'id, C, M>
+where
+    C: GetUnchecked,
+    W: IndexWidth,
+{
+    type Output = C::Item;
+
+    #[inline(always)]
+    fn index(&self, index: CompactIndex<'id, W, NonEmpty, Tag>) -> &Self::Output {
+        &self[index.index()]
+    }
+}
+
+impl<'id, C, W, Tag, M> ops::IndexMut<CompactIndex<'id, W, NonEmpty, Tag>> for Container<'id, C, M>
+where
+    C: GetUncheckedMut,
+    W: IndexWidth,
+{
+    #[inline(always)]
+    fn index_mut(&mut self, index: CompactIndex<'id, W, NonEmpty, Tag>) -> &mut Self::Output {
+        &mut self[index.index()]
+    }
+}
+
+impl<'id, T, C, P, Tag, M> ops::Index<Range<'id, P, Tag>> for Container<'id, C, M>
 where
     C: Contiguous<Item = T>,
 {
     type Output = [T];
 
     #[inline(always)]
-    fn index(&self, r: Range<'id, P>) -> &Self::Output {
+    fn index(&self, r: Range<'id, P, Tag>) -> &Self::Output {
         use std::slice;
-        unsafe { slice::from_raw_parts(self.container.begin().offset(r.start() as isize), r.len()) }
+        unsafe { slice::from_raw_parts(self.container.begin().add(r.start()), r.len()) }
     }
 }
 
-impl<'id, C, P> ops::IndexMut<Range<'id, P>> for Container<'id, C>
+impl<'id, C, P, Tag, M> ops::IndexMut<Range<'id, P, Tag>> for Container<'id, C, M>
 where
     C: ContiguousMut,
 {
     #[inline(always)]
-    fn index_mut(&mut self, r: Range<'id, P>) -> &mut Self::Output {
+    fn index_mut(&mut self, r: Range<'id, P, Tag>) -> &mut Self::Output {
         use std::slice;
-        unsafe {
-            slice::from_raw_parts_mut(
-                self.container.begin_mut().offset(r.start() as isize),
-                r.len(),
-            )
-        }
+        unsafe { slice::from_raw_parts_mut(self.container.begin_mut().add(r.start()), r.len()) }
     }
 }
 
-impl<'id, T, P, C> ops::Index<ops::RangeFrom<Index<'id, P>>> for Container<'id, C>
+impl<'id, T, P, C, Tag, M> ops::Index<ops::RangeFrom<Index<'id, P, Tag>>> for Container<'id, C, M>
 where
     C: Contiguous<Item = T>,
 {
     type Output = [T];
 
     #[inline(always)]
-    fn index(&self, r: ops::RangeFrom<Index<'id, P>>) -> &Self::Output {
+    fn index(&self, r: ops::RangeFrom<Index<'id, P, Tag>>) -> &Self::Output {
         use std::slice;
         let i = r.start.index;
 
-        unsafe { slice::from_raw_parts(self.container.begin().offset(i as isize), self.len() - i) }
+        unsafe { slice::from_raw_parts(self.container.begin().add(i), self.len() - i) }
     }
 }
 
-impl<'id, P, C> ops::IndexMut<ops::RangeFrom<Index<'id, P>>> for Container<'id, C>
+impl<'id, P, C, Tag, M> ops::IndexMut<ops::RangeFrom<Index<'id, P, Tag>>> for Container<'id, C, M>
 where
     C: ContiguousMut,
 {
     #[inline(always)]
-    fn index_mut(&mut self, r: ops::RangeFrom<Index<'id, P>>) -> &mut Self::Output {
+    fn index_mut(&mut self, r: ops::RangeFrom<Index<'id, P, Tag>>) -> &mut Self::Output {
         use std::slice;
         let i = r.start.index;
 
-        unsafe {
-            slice::from_raw_parts_mut(
-                self.container.begin_mut().offset(i as isize),
-                self.len() - i,
-            )
-        }
+        unsafe { slice::from_raw_parts_mut(self.container.begin_mut().add(i), self.len() - i) }
     }
 }
 
-impl<'id, T, P, C> ops::Index<ops::RangeTo<Index<'id, P>>> for Container<'id, C>
+impl<'id, T, P, C, Tag, M> ops::Index<ops::RangeTo<Index<'id, P, Tag>>> for Container<'id, C, M>
 where
     C: Contiguous<Item = T>,
 {
     type Output = [T];
 
     #[inline(always)]
-    fn index(&self, r: ops::RangeTo<Index<'id, P>>) -> &Self::Output {
+    fn index(&self, r: ops::RangeTo<Index<'id, P, Tag>>) -> &Self::Output {
         use std::slice;
         let i = r.end.index;
 
@@ -214,12 +1055,12 @@ where
     }
 }
 
-impl<'id, P, C> ops::IndexMut<ops::RangeTo<Index<'id, P>>> for Container<'id, C>
+impl<'id, P, C, Tag, M> ops::IndexMut<ops::RangeTo<Index<'id, P, Tag>>> for Container<'id, C, M>
 where
     C: ContiguousMut,
 {
     #[inline(always)]
-    fn index_mut(&mut self, r: ops::RangeTo<Index<'id, P>>) -> &mut Self::Output {
+    fn index_mut(&mut self, r: ops::RangeTo<Index<'id, P, Tag>>) -> &mut Self::Output {
         use std::slice;
         let i = r.end.index;
 
@@ -227,7 +1068,7 @@ where
     }
 }
 
-impl<'id, T, C> ops::Index<ops::RangeFull> for Container<'id, C>
+impl<'id, T, C, M> ops::Index<ops::RangeFull> for Container<'id, C, M>
 where
     C: Contiguous<Item = T>,
 {
@@ -239,7 +1080,7 @@ where
     }
 }
 
-impl<'id, C> ops::IndexMut<ops::RangeFull> for Container<'id, C>
+impl<'id, C, M> ops::IndexMut<ops::RangeFull> for Container<'id, C, M>
 where
     C: ContiguousMut,
 {
@@ -257,5 +1098,525 @@ where
     f(Container {
         _id: Id::new(),
         container,
+        mode: PhantomData,
+    })
+}
+
+impl<'id, C> Container<'id, C> {
+    /// Creates a new container branded with `id`, used by the `brand!` macro.
+    /// This function is marked unsafe,
+    /// because `id` is not known to be unique.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn with_id(id: Id<'id>, container: C) -> Container<'id, C> {
+        Container {
+            _id: id,
+            container,
+            mode: PhantomData,
+        }
+    }
+}
+
+/// Like `scope`, but `f` is an async closure,
+/// so the branded container can be held across `.await` points.
+///
+/// Each call still hands out a unique brand:
+///
+/// ```compile_fail
+/// use trusted::container::container::scope_async;
+///
+/// async fn mix() {
+///     scope_async(vec![1, 2], async |a| {
+///         let first = a.range().nonempty().unwrap().first();
+///         scope_async(vec![3, 4], async |b| b[first]).await
+///     })
+///     .await;
+/// }
+/// ```
+pub async fn scope_async<C, F, Out>(container: C, f: F) -> Out
+where
+    F: for<'id> AsyncFnOnce(Container<'id, C>) -> Out,
+    C: ContainerTrait,
+{
+    f(Container {
+        _id: Id::new(),
+        container,
+        mode: PhantomData,
+    })
+    .await
+}
+
+/// Like `scope`, but the container can grow by appending elements.
+/// Indices handed out before an append stay valid after it.
+pub fn scope_append_only<T, F, Out>(container: Vec<T>, f: F) -> Out
+where
+    F: for<'id> FnOnce(Container<'id, Vec<T>, AppendOnly>) -> Out,
+{
+    f(Container {
+        _id: Id::new(),
+        container,
+        mode: PhantomData,
+    })
+}
+
+/// Scopes both `a` and `b`, each with its own brand.
+pub fn scope2<A, B, F, Out>(a: A, b: B, f: F) -> Out
+where
+    F: for<'a, 'b> FnOnce(Container<'a, A>, Container<'b, B>) -> Out,
+    A: ContainerTrait,
+    B: ContainerTrait,
+{
+    scope(a, |a| scope(b, |b| f(a, b)))
+}
+
+/// Scopes `a`, `b` and `c`, each with its own brand.
+pub fn scope3<A, B, C, F, Out>(a: A, b: B, c: C, f: F) -> Out
+where
+    F: for<'a, 'b, 'c> FnOnce(Container<'a, A>, Container<'b, B>, Container<'c, C>) -> Out,
+    A: ContainerTrait,
+    B: ContainerTrait,
+    C: ContainerTrait,
+{
+    scope(a, |a| scope(b, |b| scope(c, |c| f(a, b, c))))
+}
+
+/// Scopes any number of containers at once, each with its own brand.
+///
+/// `scope_many!([a, b, c], |a, b, c| body)` expands to
+/// `scope(a, |a| scope(b, |b| scope(c, |c| body)))`.
+/// An array with a length only known at runtime can not get a brand per element,
+/// therefore the containers are listed in the invocation.
+#[macro_export]
+macro_rules! scope_many {
+    ([$($container:expr),* $(,)?], |$($name:pat_param),*| $body:expr) => {
+        $crate::scope_many!(@nest [$($container),*] [$($name),*] $body)
+    };
+    (@nest [] [] $body:expr) => {
+        $body
+    };
+    (@nest [$container:expr $(, $containers:expr)*] [$name:pat_param $(, $names:pat_param)*] $body:expr) => {
+        $crate::container::container::scope($container, |$name| {
+            $crate::scope_many!(@nest [$($containers),*] [$($names),*] $body)
+        })
+    };
+}
+
+/// Scopes an array of containers as a single container,
+/// handing out the index of every element.
+///
+/// Unlike `scope_many!`, the containers do not get a brand of their own,
+/// the brand belongs to the array. Each container can be scoped on its own
+/// after picking it with one of the indices.
+pub fn scope_array<C, F, Out, const N: usize>(containers: [C; N], f: F) -> Out
+where
+    F: for<'id> FnOnce(Container<'id, [C; N]>, [Index<'id>; N]) -> Out,
+{
+    let indices = std::array::from_fn(|i| unsafe { Index::new(i) });
+
+    f(
+        Container {
+            _id: Id::new(),
+            container: containers,
+            mode: PhantomData,
+        },
+        indices,
+    )
+}
+
+/// Scopes both `a` and `b`, calling `f` only if they have the same length.
+/// Returns None if the lengths differ.
+pub fn scope_same_len<A, B, F, Out>(a: A, b: B, f: F) -> Option<Out>
+where
+    F: for<'a, 'b> FnOnce(Container<'a, A>, Container<'b, B>, SameLen<'a, 'b>) -> Out,
+    A: ContainerTrait,
+    B: ContainerTrait,
+{
+    scope(a, |a| {
+        scope(b, |b| {
+            let proof = a.same_len(&b)?;
+            Some(f(a, b, proof))
+        })
     })
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+    };
+
+    #[test]
+    fn test_gather_scatter() {
+        scope(vec![10, 20, 30, 40], |mut v| {
+            let err = v.vet_all(&[0, 4, 2, 7]).err().unwrap();
+            assert_eq!((err.index(), err.container_len()), (4, 4));
+
+            let indices = v.vet_all(&[3, 0, 3]).unwrap();
+            let mut out = [0; 2];
+            assert_eq!(v.gather(&indices, &mut out), 2);
+            assert_eq!(out, [40, 10]);
+
+            assert_eq!(v.scatter(&indices, &[1, 2, 3, 4]), 3);
+            assert_eq!(v[..], [2, 20, 30, 3]);
+        });
+    }
+
+    #[test]
+    fn test_recover_from_ref() {
+        let other = [1, 2];
+
+        scope(&[1, 2, 3, 4][..], |v| {
+            let elt = v[..].iter().find(|&&x| x == 3).unwrap();
+            let i = v.index_of_ref(elt).unwrap();
+            assert_eq!(i.integer(), 2);
+            assert!(v.index_of_ref(&other[0]).is_none());
+
+            let r = v.range_of_subslice(&v[..][1..3]).unwrap();
+            assert_eq!((r.start(), r.end()), (1, 3));
+
+            let r = v.range_of_subslice(&v[..][4..]).unwrap();
+            assert!(r.is_empty());
+            assert!(v.range_of_subslice(&other).is_none());
+        });
+
+        scope(vec![(); 3], |v| {
+            assert!(v.index_of_ref(&v[..][1]).is_none());
+        });
+    }
+
+    #[test]
+    fn test_vecdeque() {
+        use std::collections::VecDeque;
+
+        // fill the buffer, then rotate it so it wraps around.
+        let mut deque = VecDeque::with_capacity(4);
+        deque.extend(0..deque.capacity());
+        deque.rotate_left(2);
+        deque.pop_front();
+        deque.push_back(deque.len());
+        let expected = deque.iter().copied().collect::<Vec<_>>();
+        assert!(!deque.as_slices().1.is_empty());
+
+        scope(deque, |mut v| {
+            let range = v.range().nonempty().unwrap();
+            assert_eq!(v[range.last()], expected[expected.len() - 1]);
+
+            let (front, back) = v.as_slices(range);
+            assert_eq!([front, back].concat(), expected);
+
+            let (_, tail, _) = range.split_at(v.len() - 1);
+            let (front, back) = v.as_mut_slices(tail);
+            front[0] = 42;
+            assert!(back.is_empty());
+            assert_eq!(v[range.last()], 42);
+        });
+    }
+
+    #[test]
+    fn test_position() {
+        scope(&[1, 4, 2, 4, 3][..], |v| {
+            let r = v.range();
+
+            assert_eq!(v.position(r, |&x| x == 4).map(|i| i.integer()), Some(1));
+            assert_eq!(v.rposition(r, |&x| x == 4).map(|i| i.integer()), Some(3));
+            assert!(v.position(r, |&x| x == 5).is_none());
+            assert_eq!(v.count_if(r, |&x| x > 2), 3);
+        });
+    }
+
+    #[test]
+    fn test_min_max_element() {
+        scope(&[3, 1, 4, 1, 5, 9, 2, 9][..], |v| {
+            let r = v.range().nonempty().unwrap();
+
+            let min = v.min_element_by(r, Ord::cmp);
+            let max = v.max_element_by(r, Ord::cmp);
+            assert_eq!((min.integer(), max.integer()), (1, 7));
+
+            let (min, max) = v.minmax_element(r);
+            assert_eq!((v[min], v[max]), (1, 9));
+            assert_eq!((min.integer(), max.integer()), (1, 7));
+        });
+    }
+
+    #[test]
+    fn test_scan() {
+        scope(&mut [1, 2, 2, 2, 3, 2][..], |mut v| {
+            let i = v.position(v.range(), |&x| x == 2).unwrap();
+            let j = v.rposition(v.range(), |&x| x == 2).unwrap();
+
+            let run = v.scan_from(i, |&x| x == 2);
+            assert_eq!((run.start(), run.end()), (1, 4));
+
+            let run = v.scan_from_rev(j, |&x| x == 2);
+            assert_eq!((run.start(), run.end()), (5, 6));
+
+            let (_, r) = v.split_at(i);
+            let (r, _, _) = r.split_at(2);
+            let run = v.scan_within(r, i, |_, &x| x == 2).unwrap();
+            assert_eq!((run.start(), run.end()), (1, 3));
+
+            let run = v
+                .scan_within_rev(v.range(), run.last(), |idx, _| idx >= i)
+                .unwrap();
+            assert_eq!((run.start(), run.end()), (1, 3));
+
+            // `first` and `j` lie outside of `r`, so neither scan may start from them.
+            let first = v.range().nonempty().unwrap().first();
+            assert!(v.scan_within(r, first, |_, _| true).is_none());
+            assert!(v.scan_within(r, j, |_, _| true).is_none());
+            assert!(v.scan_within_rev(r, first, |_, _| true).is_none());
+            assert!(v.scan_within_rev(r, j, |_, _| true).is_none());
+
+            let run = v.scan_from_mut(i, |x| {
+                *x *= 10;
+                true
+            });
+            assert_eq!((run.start(), run.end()), (1, 6));
+            assert_eq!(v[..], [1, 2, 20, 20, 30, 20]);
+
+            v.scan_from_rev_mut(i, |x| {
+                *x = 0;
+                false
+            });
+            assert_eq!(v[..], [0, 2, 20, 20, 30, 20]);
+        });
+    }
+
+    #[test]
+    fn test_dedup_by() {
+        scope(vec![1, 1, 2, 3, 3, 3, 1], |mut v| {
+            let edge = v.dedup_by(v.range(), |a, b| a == b);
+            let (kept, _) = v.split_at(edge);
+            assert_eq!(v[kept], [1, 2, 3, 1]);
+        });
+    }
+
+    #[test]
+    fn test_dedup_by_subrange() {
+        scope(vec![1, 1, 2, 2, 5, 5, 3, 3], |mut v| {
+            // dedup 1..6, leaving the first element and the last 2 elements alone.
+            let (_, rest, _) = v.range().split_at(1);
+            let (r, _, _) = rest.split_at(5);
+
+            let edge = v.dedup_by(r, |a, b| a == b);
+            assert_eq!(edge.integer(), 4);
+
+            // the first element of `r` is kept, although it equals the one before `r`.
+            let (kept, removed, _) = r.split_at(edge.integer() - r.start());
+            assert_eq!(v[kept], [1, 2, 5]);
+
+            let mut removed = v[removed].to_vec();
+            removed.sort();
+            assert_eq!(removed, [2, 5]);
+
+            // elements outside of `r` are untouched.
+            assert_eq!(v[..][0], 1);
+            assert_eq!(v[..][6..], [3, 3]);
+        });
+    }
+
+    #[test]
+    fn test_retain_in_place() {
+        scope(vec![1, 2, 3, 4, 5, 6], |mut v| {
+            let edge = v.retain_in_place(|&x| x % 3 != 0);
+            let (kept, garbage) = v.split_at(edge);
+            assert_eq!(v[kept], [1, 2, 4, 5]);
+            v[garbage].sort();
+            assert_eq!(v[garbage], [3, 6]);
+        });
+    }
+
+    #[test]
+    fn test_partition() {
+        scope(vec![5, 1, 8, 2, 7, 3, 4], |mut v| {
+            let edge = v.partition_in_place(|&x| x < 5);
+            let (lower, upper) = v.split_at(edge);
+            assert!(v[lower].iter().all(|&x| x < 5));
+            assert!(v[upper].iter().all(|&x| x >= 5));
+            assert_eq!(lower.len(), 4);
+        });
+
+        scope(vec![5, 1, 8, 2, 7, 3, 4], |mut v| {
+            let edge = v.stable_partition(|&x| x % 2 == 0);
+            let (even, odd) = v.split_at(edge);
+            assert_eq!(v[even], [8, 2, 4]);
+            assert_eq!(v[odd], [5, 1, 7, 3]);
+        });
+    }
+
+    #[test]
+    fn test_append_only() {
+        scope_append_only(vec![1, 2], |mut v| {
+            let first = v.range().nonempty().unwrap().first();
+            let pushed = v.push(3);
+            assert_eq!(pushed.integer(), 2);
+
+            let appended = v.extend(vec![4, 5]);
+            assert_eq!((appended.start(), appended.end()), (3, 5));
+            assert_eq!(v.range().len(), 5);
+
+            v[first] = 10;
+            assert_eq!(v[pushed], 3);
+            assert_eq!(v[appended], [4, 5]);
+
+            let four = v.index_of_ref(&v[appended][0]).unwrap();
+            let sum = v.iter_indexed().map(|(_, &x)| x).sum::<i32>();
+            assert_eq!((four.integer(), sum), (3, 24));
+
+            let v = v.freeze();
+            assert!(v.width::<u8>().is_some());
+            assert_eq!(v[..], [10, 2, 3, 4, 5]);
+        });
+    }
+
+    #[test]
+    fn test_rescope() {
+        let (index, range) = scope(vec![1, 2, 3], |v| {
+            let (_, tail, _) = v.range().split_at(1);
+            let last = tail.nonempty().unwrap().last();
+            (UnbrandedIndex::from(last), UnbrandedRange::from(tail))
+        });
+
+        let v = scope(vec![1, 2, 3], |v| {
+            v.rescope(|v| {
+                assert_eq!(v.revet(index).map(|i| v[i]), Some(3));
+                assert_eq!(v.revet_range(range).map(|r| &v[r]), Some(&[2, 3][..]));
+                assert!(v.revet(UnbrandedIndex::new(3)).is_none());
+                assert!(v.revet_range(UnbrandedRange::new(2, 1)).is_none());
+                assert!(v.revet_range(UnbrandedRange::new(3, 3)).is_some());
+                v.into_inner()
+            })
+        });
+        assert_eq!(v, [1, 2, 3]);
+    }
+
+    /// A future that is pending once, forcing a suspension point.
+    struct YieldNow(bool);
+
+    impl Future for YieldNow {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+            if self.0 {
+                Poll::Ready(())
+            } else {
+                self.0 = true;
+                cx.waker().wake_by_ref();
+                Poll::Pending
+            }
+        }
+    }
+
+    /// A waker that does nothing, the executor below polls in a loop anyway.
+    fn noop_waker() -> Waker {
+        const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW, |_| {}, |_| {}, |_| {});
+        const RAW: RawWaker = RawWaker::new(std::ptr::null(), &VTABLE);
+
+        unsafe { Waker::from_raw(RAW) }
+    }
+
+    /// A minimal executor, polling `fut` until it completes.
+    fn block_on<F: Future>(fut: F) -> F::Output {
+        let mut fut = std::pin::pin!(fut);
+        let waker = noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if let Poll::Ready(out) = fut.as_mut().poll(&mut cx) {
+                return out;
+            }
+        }
+    }
+
+    fn assert_send<T: Send>(_: &T) {}
+
+    #[test]
+    fn test_scope_async() {
+        let fut = scope_async(vec![3, 1, 2], async |mut v| {
+            let range = v.range().nonempty().unwrap();
+            let min = v.min_element_by(range, |a, b| a.cmp(b));
+            YieldNow(false).await;
+            v[min] = 0;
+            v.into_inner()
+        });
+        assert_send(&fut);
+        assert_eq!(block_on(fut), [3, 0, 2]);
+    }
+
+    #[test]
+    fn test_scope2_scope3() {
+        let mut dst = [0; 3];
+        let copied = scope2(&[1, 2, 3, 4][..], &mut dst[..], |src, mut dst| {
+            let mut n = 0;
+            for (i, j) in src.zipped(&dst) {
+                dst[j] = src[i];
+                n += 1;
+            }
+            n
+        });
+        assert_eq!((copied, dst), (3, [1, 2, 3]));
+
+        let lens = scope3(vec![1], vec![1, 2], "abc", |a, b, c| {
+            (a.len(), b.len(), c.len())
+        });
+        assert_eq!(lens, (1, 2, 3));
+    }
+
+    #[test]
+    fn test_scope_many() {
+        let sum = scope_many!(
+            [vec![1, 2], vec![3], vec![4, 5, 6], vec![7]],
+            |a, b, c, mut d| {
+                d[..][0] += 1;
+                a[..]
+                    .iter()
+                    .chain(&b[..])
+                    .chain(&c[..])
+                    .chain(&d[..])
+                    .sum::<i32>()
+            }
+        );
+        assert_eq!(sum, 29);
+    }
+
+    #[test]
+    fn test_scope_array() {
+        let buffers = [vec![3, 1], vec![2], vec![]];
+
+        let sum = scope_array(buffers, |mut buffers, [a, b, c]| {
+            buffers[b].extend([4, 5]);
+            assert!(buffers[c].is_empty());
+
+            let (longest, _) = buffers
+                .iter_indexed()
+                .max_by_key(|(_, buffer)| buffer.len())
+                .unwrap();
+            assert!(longest == b);
+
+            // each container can still be scoped with a brand of its own.
+            scope(&buffers[a][..], |a| a[..].iter().sum::<i32>())
+        });
+        assert_eq!(sum, 4);
+    }
+
+    #[test]
+    fn test_swap() {
+        use std::collections::VecDeque;
+
+        scope(vec![String::from("a"), String::from("b")], |mut v| {
+            let range = v.range().nonempty().unwrap();
+            v.swap(range.first(), range.last());
+            v.swap(range.first(), range.first());
+            assert_eq!(v[..], ["b", "a"]);
+        });
+
+        scope(VecDeque::from(vec![1, 2, 3]), |mut v| {
+            let range = v.range().nonempty().unwrap();
+            v.swap(range.first(), range.last());
+            assert_eq!(v.into_inner(), [3, 2, 1]);
+        });
+    }
+}
@@ -0,0 +1,65 @@
+use crate::fundemental::index::Index;
+
+use std::slice;
+
+/// A list of indices, all validated against the container branded with `'id`.
+///
+/// Created by `Container::vet_all`.
+pub struct TrustedIndices<'id> {
+    indices: Vec<Index<'id>>,
+}
+
+impl<'id> TrustedIndices<'id> {
+    /// Creates a new list from `indices`.
+    /// This function is marked unsafe,
+    /// because `indices` are not known to be in bounds of the container.
+    #[inline]
+    pub(crate) unsafe fn new(indices: &[usize]) -> TrustedIndices<'id> {
+        TrustedIndices {
+            indices: indices.iter().map(|&i| Index::new(i)).collect(),
+        }
+    }
+
+    /// Returns the number of indices in the list.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if the list is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the indices as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[Index<'id>] {
+        &self.indices
+    }
+
+    /// Returns an Iterator over the indices.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, Index<'id>> {
+        self.indices.iter()
+    }
+}
+
+impl<'id> Clone for TrustedIndices<'id> {
+    #[inline]
+    fn clone(&self) -> Self {
+        TrustedIndices {
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+impl<'a, 'id> IntoIterator for &'a TrustedIndices<'id> {
+    type Item = &'a Index<'id>;
+    type IntoIter = slice::Iter<'a, Index<'id>>;
+
+    #[inline(always)]
+    fn into_iter(self) -> Self::IntoIter {
+        self.indices.iter()
+    }
+}
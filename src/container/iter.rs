@@ -0,0 +1,168 @@
+use crate::fundemental::{id::Id, index::Index};
+
+use std::marker::PhantomData;
+
+/// An Iterator over the elements of a container, together with their indices.
+///
+/// Created by `Container::iter_indexed`.
+pub struct IterIndexed<'a, 'id, T> {
+    _id: Id<'id>,
+    base: *const T,
+    start: usize,
+    end: usize,
+    marker: PhantomData<&'a T>,
+}
+
+impl<'a, 'id, T> IterIndexed<'a, 'id, T> {
+    /// Creates a new Iterator over `start..end`, relative to `base`.
+    /// This function is marked unsafe,
+    /// because `base`, `start` and `end` are not known to be a valid range of the container.
+    #[inline(always)]
+    pub(crate) unsafe fn new(base: *const T, start: usize, end: usize) -> Self {
+        IterIndexed {
+            _id: Id::new(),
+            base,
+            start,
+            end,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'id, T> Iterator for IterIndexed<'a, 'id, T> {
+    type Item = (Index<'id>, &'a T);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let idx = self.start;
+            self.start += 1;
+            unsafe { Some((Index::new(idx), &*self.base.add(idx))) }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<'a, 'id, T> DoubleEndedIterator for IterIndexed<'a, 'id, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            self.end -= 1;
+            unsafe { Some((Index::new(self.end), &*self.base.add(self.end))) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, 'id, T> std::iter::ExactSizeIterator for IterIndexed<'a, 'id, T> {}
+
+/// An Iterator over mutable references to the elements of a container,
+/// together with their indices.
+///
+/// Created by `Container::iter_indexed_mut`.
+pub struct IterIndexedMut<'a, 'id, T> {
+    _id: Id<'id>,
+    base: *mut T,
+    start: usize,
+    end: usize,
+    marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, 'id, T> IterIndexedMut<'a, 'id, T> {
+    /// Creates a new Iterator over `start..end`, relative to `base`.
+    /// This function is marked unsafe,
+    /// because `base`, `start` and `end` are not known to be a valid range of the container.
+    #[inline(always)]
+    pub(crate) unsafe fn new(base: *mut T, start: usize, end: usize) -> Self {
+        IterIndexedMut {
+            _id: Id::new(),
+            base,
+            start,
+            end,
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<'a, 'id, T> Iterator for IterIndexedMut<'a, 'id, T> {
+    type Item = (Index<'id>, &'a mut T);
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            let idx = self.start;
+            self.start += 1;
+            // every index is handed out once, so the references never alias.
+            unsafe { Some((Index::new(idx), &mut *self.base.add(idx))) }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.start;
+        (len, Some(len))
+    }
+}
+
+impl<'a, 'id, T> DoubleEndedIterator for IterIndexedMut<'a, 'id, T> {
+    #[inline(always)]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.start < self.end {
+            self.end -= 1;
+            unsafe { Some((Index::new(self.end), &mut *self.base.add(self.end))) }
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, 'id, T> std::iter::ExactSizeIterator for IterIndexedMut<'a, 'id, T> {}
+
+unsafe impl<'a, 'id, T: Sync> Send for IterIndexed<'a, 'id, T> {}
+unsafe impl<'a, 'id, T: Sync> Sync for IterIndexed<'a, 'id, T> {}
+unsafe impl<'a, 'id, T: Send> Send for IterIndexedMut<'a, 'id, T> {}
+unsafe impl<'a, 'id, T: Sync> Sync for IterIndexedMut<'a, 'id, T> {}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_iter_indexed() {
+        scope(vec![10, 20, 30], |mut v| {
+            let pairs = v
+                .iter_indexed()
+                .map(|(i, &x)| (i.integer(), x))
+                .collect::<Vec<_>>();
+            assert_eq!(pairs, [(0, 10), (1, 20), (2, 30)]);
+
+            for (i, x) in v.iter_indexed_mut().rev() {
+                *x += i.integer();
+            }
+            assert_eq!(v[..], [10, 21, 32]);
+
+            let (_, tail) = v.split_at(v.range().upper_middle());
+            let tail = v
+                .iter_indexed_within(tail)
+                .map(|(_, &x)| x)
+                .collect::<Vec<_>>();
+            assert_eq!(tail, [21, 32]);
+
+            let (head, _) = v.split_at(v.range().upper_middle());
+            for (_, x) in v.iter_indexed_mut_within(head) {
+                *x = 0;
+            }
+            assert_eq!(v[..], [0, 21, 32]);
+        });
+    }
+}
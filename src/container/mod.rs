@@ -1,3 +1,13 @@
+pub mod bitset;
+pub mod brand;
 pub mod container;
+pub mod indices;
+pub mod iter;
+pub mod permutation;
+pub mod rebrand;
+pub mod samelen;
+pub mod sidetable;
+pub mod split;
+pub mod string;
 pub mod traits;
 pub mod ziprange;
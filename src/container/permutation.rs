@@ -0,0 +1,223 @@
+use super::container::Container;
+use super::traits::{ContainerTrait, GetUncheckedMut};
+use crate::fundemental::index::Index;
+
+use std::ops;
+
+/// A permutation of the indices of a container.
+///
+/// Applying the permutation moves the element at position `perm[i]` to position `i`.
+/// All indices are validated once on construction,
+/// so the permutation can be applied without any boundschecks.
+pub struct Permutation<'id> {
+    indices: Vec<Index<'id>>,
+}
+
+impl<'id> Permutation<'id> {
+    /// Creates the identity permutation for `container`.
+    #[inline]
+    pub fn identity<C>(container: &Container<'id, C>) -> Permutation<'id>
+    where
+        C: ContainerTrait,
+    {
+        Permutation {
+            indices: container.range().into_iter().collect(),
+        }
+    }
+
+    /// Creates a permutation for `container` from `indices`.
+    /// Returns None if `indices` is not as long as the container,
+    /// or if any index is out of bounds or appears more than once.
+    pub fn from_vec<C>(
+        container: &Container<'id, C>,
+        indices: Vec<usize>,
+    ) -> Option<Permutation<'id>>
+    where
+        C: ContainerTrait,
+    {
+        let len = container.len();
+        if indices.len() != len {
+            return None;
+        }
+
+        let mut seen = vec![false; len];
+        for &i in &indices {
+            if i >= len || seen[i] {
+                return None;
+            }
+            seen[i] = true;
+        }
+
+        unsafe { Some(Permutation::from_vec_unchecked(indices)) }
+    }
+
+    /// Creates a permutation from `indices`, without validating them.
+    /// This function is marked unsafe,
+    /// because `indices` is not known to be a permutation of the container's indices.
+    #[inline]
+    pub unsafe fn from_vec_unchecked(indices: Vec<usize>) -> Permutation<'id> {
+        Permutation {
+            indices: indices.into_iter().map(|i| Index::new(i)).collect(),
+        }
+    }
+
+    /// Returns the length of the permutation.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.indices.len()
+    }
+
+    /// Returns `true` if the permutation is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.indices.is_empty()
+    }
+
+    /// Returns the permuted indices.
+    #[inline]
+    pub fn as_slice(&self) -> &[Index<'id>] {
+        &self.indices
+    }
+
+    /// Rearranges the elements of `container` according to the permutation.
+    pub fn apply<C>(&self, container: &mut Container<'id, C>)
+    where
+        C: GetUncheckedMut,
+    {
+        let mut visited = vec![false; self.len()];
+
+        for i in 0..self.len() {
+            if visited[i] {
+                continue;
+            }
+            visited[i] = true;
+
+            let start: Index<'id> = unsafe { Index::new(i) };
+            let mut j = start;
+            loop {
+                let k = self[j];
+                if k == start {
+                    break;
+                }
+                container.swap(j, k);
+                visited[k.integer()] = true;
+                j = k;
+            }
+        }
+    }
+
+    /// Returns the inverse permutation, which undoes applying `self`.
+    pub fn inverse(&self) -> Permutation<'id> {
+        let mut inverse = self.indices.clone();
+
+        for (i, &p) in self.indices.iter().enumerate() {
+            inverse[p.integer()] = unsafe { Index::new(i) };
+        }
+
+        Permutation { indices: inverse }
+    }
+
+    /// Returns the permutation equivalent to applying `self`, and then `other`.
+    pub fn compose(&self, other: &Permutation<'id>) -> Permutation<'id> {
+        Permutation {
+            indices: other.indices.iter().map(|&i| self[i]).collect(),
+        }
+    }
+
+    /// Decomposes the permutation into its cycles.
+    /// Each cycle starts at its lowest index, and fixed points are returned as cycles of length 1.
+    pub fn cycles(&self) -> Vec<Vec<Index<'id>>> {
+        let mut visited = vec![false; self.len()];
+        let mut cycles = Vec::new();
+
+        for i in 0..self.len() {
+            if visited[i] {
+                continue;
+            }
+
+            let start: Index<'id> = unsafe { Index::new(i) };
+            let mut cycle = vec![start];
+            visited[i] = true;
+
+            let mut j = self[start];
+            while j != start {
+                visited[j.integer()] = true;
+                cycle.push(j);
+                j = self[j];
+            }
+            cycles.push(cycle);
+        }
+
+        cycles
+    }
+}
+
+impl<'id> Clone for Permutation<'id> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Permutation {
+            indices: self.indices.clone(),
+        }
+    }
+}
+
+impl<'id> ops::Index<Index<'id>> for Permutation<'id> {
+    type Output = Index<'id>;
+
+    #[inline(always)]
+    fn index(&self, index: Index<'id>) -> &Self::Output {
+        unsafe { self.indices.get_unchecked(index.integer()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_validation() {
+        scope(vec!['a', 'b', 'c'], |v| {
+            assert!(Permutation::from_vec(&v, vec![2, 0, 1]).is_some());
+            assert!(Permutation::from_vec(&v, vec![2, 0]).is_none());
+            assert!(Permutation::from_vec(&v, vec![2, 0, 3]).is_none());
+            assert!(Permutation::from_vec(&v, vec![2, 0, 2]).is_none());
+        });
+    }
+
+    #[test]
+    fn test_apply_inverse_compose() {
+        scope(vec!['a', 'b', 'c', 'd'], |mut v| {
+            let p = Permutation::from_vec(&v, vec![2, 0, 3, 1]).unwrap();
+
+            p.apply(&mut v);
+            assert_eq!(v[..], ['c', 'a', 'd', 'b']);
+
+            p.inverse().apply(&mut v);
+            assert_eq!(v[..], ['a', 'b', 'c', 'd']);
+
+            p.compose(&p).apply(&mut v);
+            p.inverse().apply(&mut v);
+            p.inverse().apply(&mut v);
+            assert_eq!(v[..], ['a', 'b', 'c', 'd']);
+
+            let q = Permutation::from_vec(&v, vec![1, 0, 2, 3]).unwrap();
+            p.compose(&q).apply(&mut v);
+            assert_eq!(v[..], ['a', 'c', 'd', 'b']);
+        });
+    }
+
+    #[test]
+    fn test_cycles() {
+        scope(vec![0; 5], |v| {
+            let p = Permutation::from_vec(&v, vec![1, 2, 0, 3, 4]).unwrap();
+            let cycles = p
+                .cycles()
+                .into_iter()
+                .map(|c| c.iter().map(|i| i.integer()).collect::<Vec<_>>())
+                .collect::<Vec<_>>();
+
+            assert_eq!(cycles, [vec![0, 1, 2], vec![3], vec![4]]);
+        });
+    }
+}
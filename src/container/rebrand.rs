@@ -0,0 +1,165 @@
+use crate::fundemental::id::Id;
+use crate::fundemental::index::Index;
+use crate::fundemental::proof::{LengthProof, NonEmpty, Unknown};
+use crate::fundemental::range::Range;
+
+/// A map from the container branded with `'old`
+/// to the container branded with `'new`,
+/// which is the old container with `removed` elements taken out at `cut`.
+///
+/// Created by the shrinking operations of `Container`, like `Container::truncate`.
+/// Indices before the cut keep their position,
+/// indices after the removed elements shift down,
+/// and indices of removed elements do not survive.
+pub struct Rebrand<'old, 'new> {
+    _old_id: Id<'old>,
+    _new_id: Id<'new>,
+    cut: usize,
+    removed: usize,
+}
+
+impl<'old, 'new> Rebrand<'old, 'new> {
+    /// Creates a new map for `removed` elements taken out at `cut`.
+    /// This function is marked unsafe,
+    /// because it can not be proved the new container is the old container
+    /// with exactly these elements removed.
+    #[inline(always)]
+    pub(crate) const unsafe fn new(cut: usize, removed: usize) -> Rebrand<'old, 'new> {
+        Rebrand {
+            _old_id: Id::new(),
+            _new_id: Id::new(),
+            cut,
+            removed,
+        }
+    }
+
+    /// Returns the number of elements that were removed.
+    #[inline(always)]
+    pub const fn removed(&self) -> usize {
+        self.removed
+    }
+
+    /// Translates an index of the old container into an index of the new container.
+    /// Returns None if the element at `index` was removed.
+    #[inline]
+    pub const fn index<Tag>(
+        &self,
+        index: Index<'old, NonEmpty, Tag>,
+    ) -> Option<Index<'new, NonEmpty, Tag>> {
+        match self.translate(index.integer()) {
+            Some(i) => unsafe { Some(Index::new(i)) },
+            None => None,
+        }
+    }
+
+    /// Translates an edge of the old container into an edge of the new container.
+    /// Returns None if the edge lies in between removed elements.
+    #[inline]
+    pub const fn edge<Tag>(
+        &self,
+        edge: Index<'old, Unknown, Tag>,
+    ) -> Option<Index<'new, Unknown, Tag>> {
+        // the edge at the cut is still an edge of the new container.
+        if edge.integer() == self.cut {
+            return unsafe { Some(Index::new(self.cut)) };
+        }
+
+        match self.translate(edge.integer()) {
+            Some(i) => unsafe { Some(Index::new(i)) },
+            None => None,
+        }
+    }
+
+    /// Translates a range of the old container into a range of the new container.
+    /// Returns None if any element of `range` was removed.
+    /// Only proofs about the length transfer to the new container.
+    #[inline]
+    pub const fn range<P: LengthProof, Tag>(
+        &self,
+        range: Range<'old, P, Tag>,
+    ) -> Option<Range<'new, P, Tag>> {
+        if range.end() <= self.cut {
+            unsafe { Some(Range::from_any(range.start(), range.end())) }
+        } else if range.start() >= self.cut + self.removed {
+            unsafe {
+                Some(Range::from_any(
+                    range.start() - self.removed,
+                    range.end() - self.removed,
+                ))
+            }
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    const fn translate(&self, index: usize) -> Option<usize> {
+        if index < self.cut {
+            Some(index)
+        } else if index >= self.cut + self.removed {
+            Some(index - self.removed)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'old, 'new> Copy for Rebrand<'old, 'new> {}
+
+impl<'old, 'new> Clone for Rebrand<'old, 'new> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_rebrand() {
+        scope(vec![1, 2, 3, 4, 5], |v| {
+            let range = v.range().nonempty().unwrap();
+            let (first, last) = (range.first(), range.last());
+            let (head, middle, _) = v.range().split_at(1);
+            let (middle, tail, _) = middle.split_at(2);
+
+            v.remove(middle.nonempty().unwrap().first(), |v, map, removed| {
+                assert_eq!(removed, 2);
+                assert_eq!(v[..], [1, 3, 4, 5]);
+                assert_eq!(map.removed(), 1);
+
+                assert_eq!(map.index(first).map(|i| v[i]), Some(1));
+                assert_eq!(map.index(last).map(|i| v[i]), Some(5));
+                assert_eq!(map.range(head).map(|r| &v[r]), Some(&[1][..]));
+                assert_eq!(map.range(tail).map(|r| &v[r]), Some(&[4, 5][..]));
+                assert!(map.range(middle).is_none());
+            });
+        });
+    }
+
+    #[test]
+    fn test_truncate() {
+        scope(vec![1, 2, 3, 4], |v| {
+            let range = v.range().nonempty().unwrap();
+            let last = range.last();
+            let mid = v.range().split_at(2).1;
+            let end = v.range().split_at(v.len()).1.first();
+
+            v.truncate(mid.first(), |v, map| {
+                assert_eq!(v[..], [1, 2]);
+                assert!(map.index(last).is_none());
+                assert_eq!(map.edge(mid.first()).map(|e| e.integer()), Some(2));
+                assert_eq!(map.edge(end).map(|e| e.integer()), Some(2));
+
+                v.pop(|v, map, popped| {
+                    assert_eq!(popped, Some(2));
+                    assert_eq!(map.removed(), 1);
+
+                    v.clear(|v, _| assert!(v.is_empty()));
+                });
+            });
+        });
+    }
+}
@@ -0,0 +1,134 @@
+use super::ziprange::ZipRange;
+use crate::fundemental::id::Id;
+use crate::fundemental::index::Index;
+use crate::fundemental::proof::LengthProof;
+use crate::fundemental::range::Range;
+
+/// A proof that the containers branded with `'lhs` and `'rhs`
+/// have the exact same length.
+///
+/// Because both containers are equally long, any index or range
+/// of the one container is also a valid index or range of the other.
+pub struct SameLen<'lhs, 'rhs> {
+    _lhs_id: Id<'lhs>,
+    _rhs_id: Id<'rhs>,
+    len: usize,
+}
+
+impl<'lhs, 'rhs> SameLen<'lhs, 'rhs> {
+    /// Creates a new proof for containers of length `len`.
+    /// This function is marked unsafe,
+    /// because it can not be proved both containers have length `len`.
+    #[inline(always)]
+    pub(crate) const unsafe fn new(len: usize) -> SameLen<'lhs, 'rhs> {
+        SameLen {
+            _lhs_id: Id::new(),
+            _rhs_id: Id::new(),
+            len,
+        }
+    }
+
+    /// Returns the length shared by both containers.
+    #[inline(always)]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Returns `true` if both containers are empty.
+    #[inline(always)]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the same proof, with the containers swapped.
+    #[inline(always)]
+    pub const fn flip(self) -> SameLen<'rhs, 'lhs> {
+        unsafe { SameLen::new(self.len) }
+    }
+
+    /// Translates an index of the left container into an index of the right container.
+    /// Only proofs about the length transfer, a `CharBoundary` of the left container
+    /// is not known to be a char boundary of the right container.
+    ///
+    /// ```compile_fail
+    /// use trusted::container::container::scope_same_len;
+    ///
+    /// scope_same_len("éa", "aé", |a, b, proof| {
+    ///     let (i, _) = a.char_indices().nth(1).unwrap();
+    ///     let (head, _) = b.split_str_at(proof.index(i));
+    ///     let _ = b.substr(head);
+    /// });
+    /// ```
+    #[inline(always)]
+    pub const fn index<P: LengthProof>(&self, index: Index<'lhs, P>) -> Index<'rhs, P> {
+        unsafe { Index::new(index.integer()) }
+    }
+
+    /// Translates a range of the left container into a range of the right container.
+    /// Like `index`, only proofs about the length transfer.
+    #[inline(always)]
+    pub const fn range<P: LengthProof>(&self, range: Range<'lhs, P>) -> Range<'rhs, P> {
+        unsafe { Range::from_any(range.start(), range.end()) }
+    }
+
+    /// Returns the full ranges of both containers.
+    #[inline(always)]
+    pub const fn ranges(&self) -> (Range<'lhs>, Range<'rhs>) {
+        unsafe { (Range::from(0, self.len), Range::from(0, self.len)) }
+    }
+
+    /// Zips the full ranges of both containers.
+    /// Unlike `Container::zipped`, no element of either container is left out.
+    #[inline(always)]
+    pub const fn zipped(&self) -> ZipRange<'lhs, 'rhs> {
+        unsafe { ZipRange::new(0, self.len) }
+    }
+}
+
+impl<'lhs, 'rhs> Copy for SameLen<'lhs, 'rhs> {}
+
+impl<'lhs, 'rhs> Clone for SameLen<'lhs, 'rhs> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::container::{scope, scope_same_len};
+
+    #[test]
+    fn same_len_translates_indices() {
+        let keys = [3, 1, 2];
+        let mut values = ['c', 'a', 'b'];
+
+        scope(&keys[..], |keys| {
+            scope(&mut values[..], |mut values| {
+                let proof = keys.same_len(&values).unwrap();
+
+                for i in keys.range() {
+                    if keys[i] == 1 {
+                        values[proof.index(i)] = 'z';
+                    }
+                }
+            })
+        });
+
+        assert_eq!(values, ['c', 'z', 'b']);
+    }
+
+    #[test]
+    fn scope_same_len_requires_equal_lengths() {
+        let a = [1, 2, 3];
+        let b = [4, 5, 6];
+        let c = [7, 8];
+
+        let sum = scope_same_len(&a[..], &b[..], |a, b, proof| {
+            proof.zipped().map(|(i, j)| a[i] + b[j]).sum::<i32>()
+        });
+        assert_eq!(sum, Some(21));
+
+        assert!(scope_same_len(&a[..], &c[..], |_, _, _| ()).is_none());
+    }
+}
@@ -0,0 +1,229 @@
+use super::container::Container;
+use super::traits::ContainerTrait;
+use crate::fundemental::{
+    index::Index,
+    proof::{NonEmpty, Unknown},
+    range::Range,
+};
+
+use std::{ops, slice};
+
+/// A table holding a value for each element of the container branded with `'id`,
+/// like visited flags or distances.
+///
+/// The table has the same length as the container,
+/// so it can be indexed by `Index<'id>` without boundschecks.
+/// A table with a `Tag` only accepts indices with that same tag.
+pub struct SideTable<'id, V, Tag = ()> {
+    range: Range<'id, Unknown, Tag>,
+    values: Vec<V>,
+}
+
+impl<'id, V> SideTable<'id, V> {
+    /// Creates a new table for `container`, holding a clone of `value` for each element.
+    #[inline]
+    pub fn new<C>(container: &Container<'id, C>, value: V) -> SideTable<'id, V>
+    where
+        C: ContainerTrait,
+        V: Clone,
+    {
+        SideTable {
+            range: container.range(),
+            values: vec![value; container.len()],
+        }
+    }
+
+    /// Creates a new table for `container`, holding the default value for each element.
+    #[inline]
+    pub fn with_default<C>(container: &Container<'id, C>) -> SideTable<'id, V>
+    where
+        C: ContainerTrait,
+        V: Default,
+    {
+        SideTable::from_fn(container, |_| V::default())
+    }
+
+    /// Creates a new table for `container`, holding `f(index)` for each index of the container.
+    #[inline]
+    pub fn from_fn<C, F>(container: &Container<'id, C>, f: F) -> SideTable<'id, V>
+    where
+        C: ContainerTrait,
+        F: FnMut(Index<'id>) -> V,
+    {
+        let range = container.range();
+
+        SideTable {
+            range,
+            values: range.into_iter().map(f).collect(),
+        }
+    }
+
+    /// Creates a new table for `container` from `values`.
+    /// Returns None if `values` is not as long as the container.
+    #[inline]
+    pub fn from_vec<C>(container: &Container<'id, C>, values: Vec<V>) -> Option<SideTable<'id, V>>
+    where
+        C: ContainerTrait,
+    {
+        if values.len() == container.len() {
+            Some(SideTable {
+                range: container.range(),
+                values,
+            })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'id, V, Tag> SideTable<'id, V, Tag> {
+    /// Returns the length of the table.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the table is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns a range over the indices of the table.
+    #[inline]
+    pub fn range(&self) -> Range<'id, Unknown, Tag> {
+        self.range
+    }
+
+    /// Returns the same table, with its tag changed to `U`.
+    #[inline]
+    pub fn retag<U>(self) -> SideTable<'id, V, U> {
+        SideTable {
+            range: self.range.retag(),
+            values: self.values,
+        }
+    }
+
+    /// Returns an Iterator over the values of the table.
+    #[inline]
+    pub fn iter(&self) -> slice::Iter<'_, V> {
+        self.values.iter()
+    }
+
+    /// Returns an Iterator over mutable references to the values of the table.
+    #[inline]
+    pub fn iter_mut(&mut self) -> slice::IterMut<'_, V> {
+        self.values.iter_mut()
+    }
+
+    /// Returns the values as a slice.
+    #[inline]
+    pub fn as_slice(&self) -> &[V] {
+        &self.values
+    }
+
+    /// Consumes the table, returning the values.
+    #[inline]
+    pub fn into_vec(self) -> Vec<V> {
+        self.values
+    }
+}
+
+impl<'id, V> SideTable<'id, Option<V>> {
+    /// Creates a new table for `container`, holding None for each element.
+    #[inline]
+    pub fn none<C>(container: &Container<'id, C>) -> SideTable<'id, Option<V>>
+    where
+        C: ContainerTrait,
+    {
+        SideTable::from_fn(container, |_| None)
+    }
+}
+
+impl<'id, V: Clone, Tag> Clone for SideTable<'id, V, Tag> {
+    #[inline]
+    fn clone(&self) -> Self {
+        SideTable {
+            range: self.range,
+            values: self.values.clone(),
+        }
+    }
+}
+
+impl<'id, V, Tag> ops::Index<Index<'id, NonEmpty, Tag>> for SideTable<'id, V, Tag> {
+    type Output = V;
+
+    #[inline(always)]
+    fn index(&self, index: Index<'id, NonEmpty, Tag>) -> &Self::Output {
+        unsafe { self.values.get_unchecked(index.integer()) }
+    }
+}
+
+impl<'id, V, Tag> ops::IndexMut<Index<'id, NonEmpty, Tag>> for SideTable<'id, V, Tag> {
+    #[inline(always)]
+    fn index_mut(&mut self, index: Index<'id, NonEmpty, Tag>) -> &mut Self::Output {
+        unsafe { self.values.get_unchecked_mut(index.integer()) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_side_table() {
+        // parent pointers of a small tree, stored as plain integers.
+        scope(vec![0, 0, 1, 1, 2], |parents| {
+            let mut depth = SideTable::<usize>::with_default(&parents);
+            let mut parent = SideTable::none(&parents);
+
+            let range = parents.range().nonempty().unwrap();
+            for i in range.tail() {
+                let p = range.contains(parents[i]).unwrap();
+                parent[i] = Some(p);
+                depth[i] = depth[p] + 1;
+            }
+
+            assert_eq!(depth.as_slice(), [0, 1, 2, 2, 3]);
+            assert!(parent[range.first()].is_none());
+            assert_eq!(parent[range.last()].map(|p| p.integer()), Some(2));
+        });
+
+        scope(vec!['a', 'b'], |v| {
+            assert!(SideTable::from_vec(&v, vec![1]).is_none());
+
+            let table = SideTable::from_fn(&v, |i| v[i].to_ascii_uppercase());
+            assert_eq!(table.into_vec(), ['A', 'B']);
+        });
+    }
+
+    #[test]
+    fn test_tagged_side_table() {
+        enum Row {}
+        enum Col {}
+
+        // a 3x3 matrix, stored row major.
+        let matrix = [1, 2, 3, 4, 5, 6, 7, 8, 9];
+
+        scope(vec![(); 3], |dim| {
+            let rows = dim.range().retag::<Row>();
+            let cols = dim.range().retag::<Col>();
+
+            let mut row_sums = SideTable::new(&dim, 0).retag::<Row>();
+            let mut col_sums = SideTable::new(&dim, 0).retag::<Col>();
+
+            for r in rows {
+                for c in cols {
+                    let elt = matrix[r.integer() * 3 + c.integer()];
+                    row_sums[r] += elt;
+                    col_sums[c] += elt;
+                    // row_sums[c] += elt; <-- fails to compile, `c` is a column index!!
+                }
+            }
+
+            assert_eq!(row_sums.as_slice(), [6, 15, 24]);
+            assert_eq!(col_sums.as_slice(), [12, 15, 18]);
+        });
+    }
+}
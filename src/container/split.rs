@@ -0,0 +1,189 @@
+use super::container::{Container, Fixed};
+use super::traits::GetUnchecked;
+use crate::fundemental::proof::NonEmpty;
+use crate::fundemental::range::Range;
+
+/// An Iterator over the maximal runs of a container,
+/// in which each pair of consecutive elements satisfies a relation.
+///
+/// Created by `Container::runs_by`.
+pub struct Runs<'a, 'id, C, F, M = Fixed> {
+    container: &'a Container<'id, C, M>,
+    range: Range<'id>,
+    f: F,
+}
+
+impl<'a, 'id, C, F, M> Runs<'a, 'id, C, F, M> {
+    #[inline(always)]
+    pub(crate) fn new(container: &'a Container<'id, C, M>, range: Range<'id>, f: F) -> Self {
+        Runs {
+            container,
+            range,
+            f,
+        }
+    }
+}
+
+impl<'a, 'id, C, F, M> Iterator for Runs<'a, 'id, C, F, M>
+where
+    C: GetUnchecked,
+    F: FnMut(&C::Item, &C::Item) -> bool,
+{
+    type Item = Range<'id, NonEmpty>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        let range = self.range.nonempty()?;
+        let c = self.container;
+
+        let mut last = range.first();
+        for i in range.tail() {
+            if !(self.f)(&c[last], &c[i]) {
+                break;
+            }
+            last = i;
+        }
+
+        unsafe {
+            self.range = Range::from(last.integer() + 1, range.end());
+            Some(Range::from_ne(range.start(), last.integer() + 1))
+        }
+    }
+}
+
+/// An Iterator over the ranges of a container separated by elements
+/// that match a predicate. The separators are not contained in any range.
+///
+/// Created by `Container::split_by`.
+pub struct Split<'a, 'id, C, F, M = Fixed> {
+    container: &'a Container<'id, C, M>,
+    range: Range<'id>,
+    finished: bool,
+    pred: F,
+}
+
+impl<'a, 'id, C, F, M> Split<'a, 'id, C, F, M> {
+    #[inline(always)]
+    pub(crate) fn new(container: &'a Container<'id, C, M>, range: Range<'id>, pred: F) -> Self {
+        Split {
+            container,
+            range,
+            finished: false,
+            pred,
+        }
+    }
+
+    /// Returns the remaining range, and stops the iteration.
+    #[inline]
+    fn finish(&mut self) -> Option<Range<'id>> {
+        if self.finished {
+            None
+        } else {
+            self.finished = true;
+            Some(self.range)
+        }
+    }
+}
+
+impl<'a, 'id, C, F, M> Iterator for Split<'a, 'id, C, F, M>
+where
+    C: GetUnchecked,
+    F: FnMut(&C::Item) -> bool,
+{
+    type Item = Range<'id>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+
+        match self.container.position(self.range, &mut self.pred) {
+            Some(sep) => {
+                let (piece, rest) = self.range.split_index(sep);
+                // the separator itself is skipped.
+                self.range = unsafe { rest.nonempty_unchecked() }.tail();
+                Some(piece)
+            }
+            None => self.finish(),
+        }
+    }
+}
+
+/// An Iterator like `Split`, returning at most `n` ranges.
+/// The last range contains the remainder of the container.
+///
+/// Created by `Container::splitn`.
+pub struct SplitN<'a, 'id, C, F, M = Fixed> {
+    split: Split<'a, 'id, C, F, M>,
+    count: usize,
+}
+
+impl<'a, 'id, C, F, M> SplitN<'a, 'id, C, F, M> {
+    #[inline(always)]
+    pub(crate) fn new(split: Split<'a, 'id, C, F, M>, count: usize) -> Self {
+        SplitN { split, count }
+    }
+}
+
+impl<'a, 'id, C, F, M> Iterator for SplitN<'a, 'id, C, F, M>
+where
+    C: GetUnchecked,
+    F: FnMut(&C::Item) -> bool,
+{
+    type Item = Range<'id>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.count {
+            0 => None,
+            1 => {
+                self.count = 0;
+                self.split.finish()
+            }
+            _ => {
+                self.count -= 1;
+                self.split.next()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_runs_by() {
+        scope(&[1, 1, 2, 3, 3, 3][..], |v| {
+            let runs = v.runs_by(|a, b| a == b).map(|r| &v[r]).collect::<Vec<_>>();
+
+            assert_eq!(runs, [&[1, 1][..], &[2], &[3, 3, 3]]);
+        });
+
+        scope(&[0u8; 0][..], |v| {
+            assert!(v.runs_by(|a, b| a == b).next().is_none());
+        });
+    }
+
+    #[test]
+    fn test_split_by() {
+        scope(&b"a,bc,,d"[..], |v| {
+            let parts = v
+                .split_by(|&b| b == b',')
+                .map(|r| &v[r])
+                .collect::<Vec<_>>();
+            assert_eq!(parts, [&b"a"[..], b"bc", b"", b"d"]);
+
+            let parts = v
+                .splitn(2, |&b| b == b',')
+                .map(|r| &v[r])
+                .collect::<Vec<_>>();
+            assert_eq!(parts, [&b"a"[..], b"bc,,d"]);
+        });
+
+        scope(&b","[..], |v| {
+            assert_eq!(v.split_by(|&b| b == b',').count(), 2);
+        });
+    }
+}
@@ -0,0 +1,140 @@
+use super::container::Container;
+use super::traits::AsStr;
+use crate::fundemental::{id::Id, index::Index, proof::CharBoundary, range::Range};
+
+use std::str;
+
+impl<'id, C, M> Container<'id, C, M>
+where
+    C: AsStr,
+{
+    /// Returns the container as a string slice.
+    #[inline(always)]
+    pub fn as_str(&self) -> &str {
+        unsafe { str::from_utf8_unchecked(&self[..]) }
+    }
+
+    /// Returns a range over the whole string.
+    #[inline(always)]
+    pub fn char_range(&self) -> Range<'id, CharBoundary> {
+        unsafe { Range::from_any(0, self.len()) }
+    }
+
+    /// Returns Some if `index` lies on a char boundary, None otherwise.
+    /// The length of the string is a char boundary as well.
+    #[inline]
+    pub fn char_boundary<P>(&self, index: Index<'id, P>) -> Option<Index<'id, CharBoundary>> {
+        if self.as_str().is_char_boundary(index.integer()) {
+            unsafe { Some(Index::new(index.integer())) }
+        } else {
+            None
+        }
+    }
+
+    /// Returns the range from `start` to `end`,
+    /// or None if `start` comes after `end`.
+    #[inline]
+    pub fn char_range_between(
+        &self,
+        start: Index<'id, CharBoundary>,
+        end: Index<'id, CharBoundary>,
+    ) -> Option<Range<'id, CharBoundary>> {
+        if start <= end {
+            unsafe { Some(Range::from_any(start.integer(), end.integer())) }
+        } else {
+            None
+        }
+    }
+
+    /// Splits the string at `index`, returning 2 ranges into the string.
+    #[inline]
+    pub fn split_str_at(
+        &self,
+        index: Index<'id, CharBoundary>,
+    ) -> (Range<'id, CharBoundary>, Range<'id, CharBoundary>) {
+        unsafe {
+            (
+                Range::from_any(0, index.integer()),
+                Range::from_any(index.integer(), self.len()),
+            )
+        }
+    }
+
+    /// Returns the string slice of `r`, without any boundary or bounds check.
+    #[inline(always)]
+    pub fn substr(&self, r: Range<'id, CharBoundary>) -> &str {
+        unsafe { str::from_utf8_unchecked(&self[r]) }
+    }
+
+    /// Returns an Iterator over the chars of the string, together with their positions.
+    #[inline]
+    pub fn char_indices(&self) -> CharIndices<'_, 'id> {
+        CharIndices {
+            _id: Id::new(),
+            inner: self.as_str().char_indices(),
+        }
+    }
+}
+
+/// An Iterator over the chars of a string, together with their positions.
+///
+/// Created by `Container::char_indices`.
+pub struct CharIndices<'a, 'id> {
+    _id: Id<'id>,
+    inner: str::CharIndices<'a>,
+}
+
+impl<'a, 'id> Iterator for CharIndices<'a, 'id> {
+    type Item = (Index<'id, CharBoundary>, char);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next()
+            .map(|(i, c)| unsafe { (Index::new(i), c) })
+    }
+}
+
+impl<'a, 'id> DoubleEndedIterator for CharIndices<'a, 'id> {
+    #[inline]
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.inner
+            .next_back()
+            .map(|(i, c)| unsafe { (Index::new(i), c) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_char_boundaries() {
+        scope("héllo wörld", |s| {
+            let (space, _) = s.char_indices().find(|&(_, c)| c == ' ').unwrap();
+            let (head, tail) = s.split_str_at(space);
+            assert_eq!(s.substr(head), "héllo");
+            assert_eq!(s.substr(tail), " wörld");
+
+            // the second byte of 'é' is not a char boundary.
+            let range = s.range().nonempty().unwrap();
+            let (_, rest, _) = range.split_at(2);
+            assert!(s.char_boundary(rest.first()).is_none());
+            assert!(s.char_boundary(s.range().first()).is_some());
+
+            let (o, _) = s.char_indices().rev().find(|&(_, c)| c == 'ö').unwrap();
+            let word = s.char_range_between(space, o).unwrap();
+            assert_eq!(s.substr(word), " w");
+            assert!(s.char_range_between(o, space).is_none());
+        });
+
+        scope(String::from("añb"), |s| {
+            let parts = s
+                .char_indices()
+                .map(|(i, _)| i.integer())
+                .collect::<Vec<_>>();
+            assert_eq!(parts, [0, 1, 3]);
+            assert_eq!(s.substr(s.char_range()), "añb");
+        });
+    }
+}
@@ -1,3 +1,5 @@
+use std::{borrow::Cow, collections::VecDeque, rc::Rc, sync::Arc};
+
 /// The most basic container.
 /// The container hold elements of type `Item`.
 ///
@@ -9,7 +11,7 @@ pub unsafe trait ContainerTrait {
     fn base_len(&self) -> usize;
 }
 
-unsafe impl<'a, C: ?Sized + ContainerTrait> ContainerTrait for &'a C {
+unsafe impl<C: ?Sized + ContainerTrait> ContainerTrait for &C {
     type Item = C::Item;
 
     #[inline(always)]
@@ -18,7 +20,7 @@ unsafe impl<'a, C: ?Sized + ContainerTrait> ContainerTrait for &'a C {
     }
 }
 
-unsafe impl<'a, C: ?Sized + ContainerTrait> ContainerTrait for &'a mut C {
+unsafe impl<C: ?Sized + ContainerTrait> ContainerTrait for &mut C {
     type Item = C::Item;
 
     #[inline(always)]
@@ -33,7 +35,7 @@ pub unsafe trait Contiguous: ContainerTrait {
     fn as_slice(&self) -> &[Self::Item];
 }
 
-unsafe impl<'a, C: ?Sized + Contiguous> Contiguous for &'a C {
+unsafe impl<C: ?Sized + Contiguous> Contiguous for &C {
     #[inline(always)]
     fn begin(&self) -> *const Self::Item {
         (**self).begin()
@@ -50,7 +52,7 @@ unsafe impl<'a, C: ?Sized + Contiguous> Contiguous for &'a C {
     }
 }
 
-unsafe impl<'a, C: ?Sized + Contiguous> Contiguous for &'a mut C {
+unsafe impl<C: ?Sized + Contiguous> Contiguous for &mut C {
     #[inline(always)]
     fn begin(&self) -> *const Self::Item {
         (**self).begin()
@@ -81,7 +83,7 @@ pub unsafe trait ContiguousMut: Contiguous {
     fn as_mut_slice(&mut self) -> &mut [Self::Item];
 }
 
-unsafe impl<'a, C: ?Sized + ContiguousMut> ContiguousMut for &'a mut C {
+unsafe impl<C: ?Sized + ContiguousMut> ContiguousMut for &mut C {
     #[inline(always)]
     fn as_mut_slice(&mut self) -> &mut [Self::Item] {
         (**self).as_mut_slice()
@@ -89,18 +91,17 @@ unsafe impl<'a, C: ?Sized + ContiguousMut> ContiguousMut for &'a mut C {
 }
 
 pub unsafe trait GetUnchecked: ContainerTrait {
-    #[inline(always)]
     unsafe fn unchecked(&self, index: usize) -> &Self::Item;
 }
 
-unsafe impl<'a, C: ?Sized + GetUnchecked> GetUnchecked for &'a C {
+unsafe impl<C: ?Sized + GetUnchecked> GetUnchecked for &C {
     #[inline(always)]
     unsafe fn unchecked(&self, index: usize) -> &Self::Item {
         (**self).unchecked(index)
     }
 }
 
-unsafe impl<'a, C: ?Sized + GetUnchecked> GetUnchecked for &'a mut C {
+unsafe impl<C: ?Sized + GetUnchecked> GetUnchecked for &mut C {
     #[inline(always)]
     unsafe fn unchecked(&self, index: usize) -> &Self::Item {
         (**self).unchecked(index)
@@ -108,11 +109,10 @@ unsafe impl<'a, C: ?Sized + GetUnchecked> GetUnchecked for &'a mut C {
 }
 
 pub unsafe trait GetUncheckedMut: GetUnchecked {
-    #[inline(always)]
     unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item;
 }
 
-unsafe impl<'a, C: ?Sized + GetUncheckedMut> GetUncheckedMut for &'a mut C {
+unsafe impl<C: ?Sized + GetUncheckedMut> GetUncheckedMut for &mut C {
     #[inline(always)]
     unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item {
         (**self).unchecked_mut(index)
@@ -136,7 +136,7 @@ unsafe impl<T> Contiguous for [T] {
 
     #[inline(always)]
     fn end(&self) -> *const Self::Item {
-        unsafe { self.begin().offset(self.len() as isize) }
+        unsafe { self.begin().add(self.len()) }
     }
 
     #[inline(always)]
@@ -183,7 +183,7 @@ unsafe impl<T> Contiguous for Vec<T> {
 
     #[inline(always)]
     fn end(&self) -> *const Self::Item {
-        unsafe { self.begin().offset(self.len() as isize) }
+        unsafe { self.begin().add(self.len()) }
     }
 
     #[inline(always)]
@@ -212,3 +212,279 @@ unsafe impl<T> GetUncheckedMut for Vec<T> {
         self.get_unchecked_mut(index)
     }
 }
+
+/// A container of bytes, that are guaranteed to be valid UTF-8.
+pub unsafe trait AsStr: Contiguous<Item = u8> {
+    fn as_str(&self) -> &str;
+}
+
+unsafe impl<C: ?Sized + AsStr> AsStr for &C {
+    #[inline(always)]
+    fn as_str(&self) -> &str {
+        (**self).as_str()
+    }
+}
+
+unsafe impl<C: ?Sized + AsStr> AsStr for &mut C {
+    #[inline(always)]
+    fn as_str(&self) -> &str {
+        (**self).as_str()
+    }
+}
+
+unsafe impl ContainerTrait for str {
+    type Item = u8;
+
+    #[inline(always)]
+    fn base_len(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl Contiguous for str {
+    #[inline(always)]
+    fn begin(&self) -> *const Self::Item {
+        self.as_ptr()
+    }
+
+    #[inline(always)]
+    fn end(&self) -> *const Self::Item {
+        unsafe { self.begin().add(self.len()) }
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[Self::Item] {
+        self.as_bytes()
+    }
+}
+
+unsafe impl GetUnchecked for str {
+    #[inline(always)]
+    unsafe fn unchecked(&self, index: usize) -> &Self::Item {
+        self.as_bytes().get_unchecked(index)
+    }
+}
+
+unsafe impl AsStr for str {
+    #[inline(always)]
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+unsafe impl ContainerTrait for String {
+    type Item = u8;
+
+    #[inline(always)]
+    fn base_len(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl Contiguous for String {
+    #[inline(always)]
+    fn begin(&self) -> *const Self::Item {
+        self.as_ptr()
+    }
+
+    #[inline(always)]
+    fn end(&self) -> *const Self::Item {
+        unsafe { self.begin().add(self.len()) }
+    }
+
+    #[inline(always)]
+    fn as_slice(&self) -> &[Self::Item] {
+        self.as_bytes()
+    }
+}
+
+unsafe impl GetUnchecked for String {
+    #[inline(always)]
+    unsafe fn unchecked(&self, index: usize) -> &Self::Item {
+        self.as_bytes().get_unchecked(index)
+    }
+}
+
+unsafe impl AsStr for String {
+    #[inline(always)]
+    fn as_str(&self) -> &str {
+        self
+    }
+}
+
+/// A container whose elements are stored in two contiguous slices,
+/// like a ring buffer. The elements of the first slice precede those of the second.
+pub unsafe trait AsSlices: ContainerTrait {
+    fn as_slices(&self) -> (&[Self::Item], &[Self::Item]);
+}
+
+unsafe impl<C: ?Sized + AsSlices> AsSlices for &C {
+    #[inline(always)]
+    fn as_slices(&self) -> (&[Self::Item], &[Self::Item]) {
+        (**self).as_slices()
+    }
+}
+
+unsafe impl<C: ?Sized + AsSlices> AsSlices for &mut C {
+    #[inline(always)]
+    fn as_slices(&self) -> (&[Self::Item], &[Self::Item]) {
+        (**self).as_slices()
+    }
+}
+
+pub unsafe trait AsSlicesMut: AsSlices {
+    fn as_mut_slices(&mut self) -> (&mut [Self::Item], &mut [Self::Item]);
+}
+
+unsafe impl<C: ?Sized + AsSlicesMut> AsSlicesMut for &mut C {
+    #[inline(always)]
+    fn as_mut_slices(&mut self) -> (&mut [Self::Item], &mut [Self::Item]) {
+        (**self).as_mut_slices()
+    }
+}
+
+unsafe impl<T> ContainerTrait for VecDeque<T> {
+    type Item = T;
+
+    #[inline(always)]
+    fn base_len(&self) -> usize {
+        self.len()
+    }
+}
+
+unsafe impl<T> AsSlices for VecDeque<T> {
+    #[inline(always)]
+    fn as_slices(&self) -> (&[Self::Item], &[Self::Item]) {
+        self.as_slices()
+    }
+}
+
+unsafe impl<T> AsSlicesMut for VecDeque<T> {
+    #[inline(always)]
+    fn as_mut_slices(&mut self) -> (&mut [Self::Item], &mut [Self::Item]) {
+        self.as_mut_slices()
+    }
+}
+
+unsafe impl<T> GetUnchecked for VecDeque<T> {
+    #[inline(always)]
+    unsafe fn unchecked(&self, index: usize) -> &Self::Item {
+        let (front, back) = self.as_slices();
+
+        if index < front.len() {
+            front.get_unchecked(index)
+        } else {
+            back.get_unchecked(index - front.len())
+        }
+    }
+}
+
+unsafe impl<T> GetUncheckedMut for VecDeque<T> {
+    #[inline(always)]
+    unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item {
+        let (front, back) = self.as_mut_slices();
+
+        if index < front.len() {
+            front.get_unchecked_mut(index)
+        } else {
+            back.get_unchecked_mut(index - front.len())
+        }
+    }
+}
+
+/// Implements `ContainerTrait`, `Contiguous` and `GetUnchecked`
+/// for a type that can be viewed as a slice.
+macro_rules! slice_like {
+    ([$($gen:tt)*] $ty:ty, [$($bound:tt)*]) => {
+        unsafe impl<$($gen)*> ContainerTrait for $ty where $($bound)* {
+            type Item = T;
+
+            #[inline(always)]
+            fn base_len(&self) -> usize {
+                self[..].len()
+            }
+        }
+
+        unsafe impl<$($gen)*> Contiguous for $ty where $($bound)* {
+            #[inline(always)]
+            fn begin(&self) -> *const Self::Item {
+                self[..].as_ptr()
+            }
+
+            #[inline(always)]
+            fn end(&self) -> *const Self::Item {
+                self[..].as_ptr_range().end
+            }
+
+            #[inline(always)]
+            fn as_slice(&self) -> &[Self::Item] {
+                &self[..]
+            }
+        }
+
+        unsafe impl<$($gen)*> GetUnchecked for $ty where $($bound)* {
+            #[inline(always)]
+            unsafe fn unchecked(&self, index: usize) -> &Self::Item {
+                self[..].get_unchecked(index)
+            }
+        }
+    };
+}
+
+/// Implements `ContiguousMut` and `GetUncheckedMut`
+/// for a type that can be viewed as a mutable slice.
+macro_rules! slice_like_mut {
+    ([$($gen:tt)*] $ty:ty) => {
+        slice_like!([$($gen)*] $ty, []);
+
+        unsafe impl<$($gen)*> ContiguousMut for $ty {
+            #[inline(always)]
+            fn as_mut_slice(&mut self) -> &mut [Self::Item] {
+                &mut self[..]
+            }
+        }
+
+        unsafe impl<$($gen)*> GetUncheckedMut for $ty {
+            #[inline(always)]
+            unsafe fn unchecked_mut(&mut self, index: usize) -> &mut Self::Item {
+                self[..].get_unchecked_mut(index)
+            }
+        }
+    };
+}
+
+slice_like_mut!([T, const N: usize] [T; N]);
+slice_like_mut!([T] Box<[T]>);
+slice_like_mut!([T, const N: usize] Box<[T; N]>);
+
+// Shared and possibly borrowed storage is read-only.
+slice_like!([T] Rc<[T]>, []);
+slice_like!([T] Arc<[T]>, []);
+slice_like!(['a, T] Cow<'a, [T]>, [T: Clone]);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::scope;
+
+    fn sum<C: GetUnchecked<Item = i32>>(c: C) -> i32 {
+        scope(c, |c| c.range().into_iter().map(|i| c[i]).sum())
+    }
+
+    #[test]
+    fn test_owned_storage() {
+        assert_eq!(sum([1, 2, 3]), 6);
+        assert_eq!(sum(Box::new([1, 2, 3])), 6);
+        assert_eq!(sum(vec![1, 2, 3].into_boxed_slice()), 6);
+        assert_eq!(sum(Rc::<[i32]>::from(vec![1, 2, 3])), 6);
+        assert_eq!(sum(Arc::<[i32]>::from(vec![1, 2, 3])), 6);
+        assert_eq!(sum(Cow::Borrowed(&[1, 2, 3][..])), 6);
+        assert_eq!(sum(Cow::<[i32]>::Owned(vec![1, 2, 3])), 6);
+
+        scope([3, 2, 1], |mut v| {
+            let r = v.range();
+            v[r].sort();
+            assert_eq!(v[..], [1, 2, 3]);
+        });
+    }
+}
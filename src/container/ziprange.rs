@@ -3,21 +3,38 @@ use crate::fundemental::index::Index;
 use crate::fundemental::range::Range;
 
 /// An Iterator that zip's range's from 2 different containers.
+/// The range of the right container may be offset from the range of the left container.
 pub struct ZipRange<'lhs, 'rhs> {
     _lhs_id: Id<'lhs>,
     _rhs_id: Id<'rhs>,
     start: usize,
     end: usize,
+    // the right index is `rhs_start - lhs_start` away from the left index.
+    lhs_start: usize,
+    rhs_start: usize,
 }
 
 impl<'lhs, 'rhs> ZipRange<'lhs, 'rhs> {
     #[inline(always)]
     pub(crate) const unsafe fn new(start: usize, end: usize) -> ZipRange<'lhs, 'rhs> {
+        ZipRange::with_offsets(start, start, end - start)
+    }
+
+    /// Creates a new ZipRange over `len` elements,
+    /// starting at `lhs_start` in the left and at `rhs_start` in the right container.
+    #[inline(always)]
+    pub(crate) const unsafe fn with_offsets(
+        lhs_start: usize,
+        rhs_start: usize,
+        len: usize,
+    ) -> ZipRange<'lhs, 'rhs> {
         ZipRange {
             _lhs_id: Id::new(),
             _rhs_id: Id::new(),
-            start,
-            end,
+            start: lhs_start,
+            end: lhs_start + len,
+            lhs_start,
+            rhs_start,
         }
     }
 
@@ -26,10 +43,24 @@ impl<'lhs, 'rhs> ZipRange<'lhs, 'rhs> {
         unsafe {
             (
                 Range::from(self.start, self.end),
-                Range::from(self.start, self.end),
+                Range::from(self.rhs(self.start), self.rhs(self.end)),
             )
         }
     }
+
+    /// Translates a position in the left container into the right container.
+    #[inline(always)]
+    const fn rhs(&self, lhs: usize) -> usize {
+        lhs - self.lhs_start + self.rhs_start
+    }
+}
+
+impl<'lhs, 'rhs> Copy for ZipRange<'lhs, 'rhs> {}
+
+impl<'lhs, 'rhs> Clone for ZipRange<'lhs, 'rhs> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
 impl<'lhs, 'rhs> Iterator for ZipRange<'lhs, 'rhs> {
@@ -40,7 +71,7 @@ impl<'lhs, 'rhs> Iterator for ZipRange<'lhs, 'rhs> {
         if self.start < self.end {
             let idx = self.start;
             self.start += 1;
-            unsafe { Some((Index::new(idx), Index::new(idx))) }
+            unsafe { Some((Index::new(idx), Index::new(self.rhs(idx)))) }
         } else {
             None
         }
@@ -53,7 +84,7 @@ impl<'lhs, 'rhs> DoubleEndedIterator for ZipRange<'lhs, 'rhs> {
         if self.start < self.end {
             self.end -= 1;
 
-            unsafe { Some((Index::new(self.end), Index::new(self.end))) }
+            unsafe { Some((Index::new(self.end), Index::new(self.rhs(self.end)))) }
         } else {
             None
         }
@@ -0,0 +1,218 @@
+use super::{id::Id, index::Index, proof::NonEmpty};
+use std::{
+    cmp::Ordering,
+    fmt::{self, Debug},
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+};
+
+/// An unsigned integer type that can store an index.
+///
+/// # Safety
+/// `MAX` must be the largest value that round trips through
+/// `from_usize_unchecked` and `to_usize` unchanged.
+pub unsafe trait IndexWidth: Copy + Ord + Hash {
+    /// The largest index this type can hold.
+    const MAX: usize;
+
+    /// Converts `index` into `Self`, truncating if it does not fit.
+    fn from_usize_unchecked(index: usize) -> Self;
+
+    /// Converts `self` back into an usize.
+    fn to_usize(self) -> usize;
+}
+
+macro_rules! index_width {
+    ($($int:ty),*) => {
+        $(
+            unsafe impl IndexWidth for $int {
+                const MAX: usize = if (<$int>::MAX as u128) < (usize::MAX as u128) {
+                    <$int>::MAX as usize
+                } else {
+                    usize::MAX
+                };
+
+                #[inline(always)]
+                fn from_usize_unchecked(index: usize) -> Self {
+                    index as $int
+                }
+
+                #[inline(always)]
+                fn to_usize(self) -> usize {
+                    self as usize
+                }
+            }
+        )*
+    };
+}
+
+index_width!(u8, u16, u32, usize);
+
+/// A proof that every index of the container branded with `'id`,
+/// including the edge at its end, fits in `W`.
+pub struct Width<'id, W> {
+    _id: Id<'id>,
+    width: PhantomData<W>,
+}
+
+impl<'id, W: IndexWidth> Width<'id, W> {
+    /// Creates a new proof.
+    /// This function is marked unsafe,
+    /// because it can not be proved the container's length fits in `W`.
+    #[inline(always)]
+    pub(crate) const unsafe fn new() -> Width<'id, W> {
+        Width {
+            _id: Id::new(),
+            width: PhantomData,
+        }
+    }
+
+    /// Converts `index` into a compact index, without any check.
+    /// The proof and tag of `index` carry over to the compact index.
+    #[inline(always)]
+    pub fn compact<P, Tag>(&self, index: Index<'id, P, Tag>) -> CompactIndex<'id, W, P, Tag> {
+        CompactIndex {
+            _id: Id::new(),
+            index: W::from_usize_unchecked(index.integer()),
+            proof: PhantomData,
+            tag: PhantomData,
+        }
+    }
+}
+
+impl<'id, W> Copy for Width<'id, W> {}
+
+impl<'id, W> Clone for Width<'id, W> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+/// An index like `Index`, stored as a `W` instead of an usize.
+/// Like `Index`, it carries an optional `Tag`,
+/// and can only be compared with compact indices of the same tag.
+///
+/// Created trough a `Width` proof, obtained with `Container::width`.
+pub struct CompactIndex<'id, W, Proof = NonEmpty, Tag = ()> {
+    _id: Id<'id>,
+    index: W,
+    proof: PhantomData<Proof>,
+    tag: PhantomData<Tag>,
+}
+
+/// An index stored in a single byte.
+pub type Index8<'id> = CompactIndex<'id, u8>;
+
+/// An index stored in 2 bytes.
+pub type Index16<'id> = CompactIndex<'id, u16>;
+
+/// An index stored in 4 bytes.
+pub type Index32<'id> = CompactIndex<'id, u32>;
+
+impl<'id, W: IndexWidth, P, Tag> CompactIndex<'id, W, P, Tag> {
+    /// Converts the compact index back into an `Index`.
+    #[inline(always)]
+    pub fn index(self) -> Index<'id, P, Tag> {
+        unsafe { Index::new(self.index.to_usize()) }
+    }
+
+    /// Return the index as an integer offset from the start of the container.
+    #[inline(always)]
+    pub fn integer(&self) -> usize {
+        self.index.to_usize()
+    }
+}
+
+impl<'id, W: Copy, P, Tag> Copy for CompactIndex<'id, W, P, Tag> {}
+
+impl<'id, W: Copy, P, Tag> Clone for CompactIndex<'id, W, P, Tag> {
+    #[inline]
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'id, W: IndexWidth, P, Tag> Debug for CompactIndex<'id, W, P, Tag> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_tuple("CompactIndex")
+            .field(&self.integer())
+            .finish()
+    }
+}
+
+impl<'id, W: IndexWidth, P, Tag> PartialEq for CompactIndex<'id, W, P, Tag> {
+    #[inline(always)]
+    fn eq(&self, rhs: &Self) -> bool {
+        self.index == rhs.index
+    }
+}
+
+impl<'id, W: IndexWidth, P, Tag> Eq for CompactIndex<'id, W, P, Tag> {}
+
+impl<'id, W: IndexWidth, P, Tag> PartialOrd for CompactIndex<'id, W, P, Tag> {
+    #[inline(always)]
+    fn partial_cmp(&self, rhs: &Self) -> Option<Ordering> {
+        Some(self.cmp(rhs))
+    }
+}
+
+impl<'id, W: IndexWidth, P, Tag> Ord for CompactIndex<'id, W, P, Tag> {
+    #[inline(always)]
+    fn cmp(&self, rhs: &Self) -> Ordering {
+        self.index.cmp(&rhs.index)
+    }
+}
+
+impl<'id, W: IndexWidth, P, Tag> Hash for CompactIndex<'id, W, P, Tag> {
+    #[inline(always)]
+    fn hash<H: Hasher>(&self, h: &mut H) {
+        self.index.hash(h)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::container::container::scope;
+
+    #[test]
+    fn test_compact_indices() {
+        use std::mem::size_of;
+
+        assert_eq!(size_of::<Index32>(), 4);
+
+        scope(vec![0u32; 300], |mut v| {
+            assert!(v.width::<u8>().is_none());
+            let width = v.width::<u16>().unwrap();
+
+            let odd = v
+                .range()
+                .into_iter()
+                .filter(|i| i.integer() % 2 == 1)
+                .map(|i| width.compact(i))
+                .collect::<Vec<_>>();
+
+            for &i in &odd {
+                v[i] = i.integer() as u32;
+            }
+            assert_eq!(v[odd[149]], 299);
+            assert_eq!(v.count_if(v.range(), |&x| x != 0), 150);
+        });
+    }
+
+    #[test]
+    fn test_tagged_compact_indices() {
+        enum Row {}
+
+        scope(vec![1, 2, 3], |v| {
+            let width = v.width::<u8>().unwrap();
+            let rows = v.range().retag::<Row>();
+            let last: CompactIndex<'_, u8, NonEmpty, Row> =
+                width.compact(rows.nonempty().unwrap().last());
+
+            assert_eq!(v[last], 3);
+            assert!(last.index() == rows.nonempty().unwrap().last());
+        });
+    }
+}
@@ -15,9 +15,45 @@ pub struct Id<'id> {
 impl<'id> Id<'id> {
     /// A constant constructor, only available for the crate.
     #[inline]
-    pub(in crate) const fn new() -> Id<'id> {
+    pub(crate) const fn new() -> Id<'id> {
         Self { id: PhantomData }
     }
+
+    /// Creates a new Id, used by the `brand!` macro.
+    /// This function is marked unsafe,
+    /// because `'id` is only known to be unique when it is
+    /// tied to a `LifetimeBrand`, as the `brand!` macro does.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn unique() -> Id<'id> {
+        Self::new()
+    }
+}
+
+/// Ties `'id` to a borrow of a local `Id`, used by the `brand!` macro.
+///
+/// Because this type implements Drop, the borrow has to last
+/// until the end of the enclosing scope,
+/// which makes `'id` different from the lifetime of any other brand.
+#[doc(hidden)]
+pub struct LifetimeBrand<'id> {
+    _id: PhantomData<&'id Id<'id>>,
+}
+
+impl<'id> LifetimeBrand<'id> {
+    /// Creates a new brand for `id`.
+    /// This function is marked unsafe,
+    /// because `id` must be a local that lives until the end of the scope.
+    #[doc(hidden)]
+    #[inline(always)]
+    pub const unsafe fn new(_: &'id Id<'id>) -> LifetimeBrand<'id> {
+        LifetimeBrand { _id: PhantomData }
+    }
+}
+
+impl<'id> Drop for LifetimeBrand<'id> {
+    #[inline(always)]
+    fn drop(&mut self) {}
 }
 
 /// We hold no data, but *mut prevents Send and Sync.
@@ -1,30 +1,59 @@
-use super::{id::Id, proof::NonEmpty};
+use super::{
+    id::Id,
+    proof::{NonEmpty, Unknown},
+};
 use std::{
     cmp::Ordering,
+    error::Error,
+    fmt::{self, Display},
     hash::{Hash, Hasher},
     marker::PhantomData,
 };
 
 /// An id based index, trough which a container
 /// can be accessed without boundschecks.
+///
+/// The optional `Tag` parameter separates indices of different roles
+/// within the same branding, like the rows and columns of a square matrix.
+/// Indices with different tags can not be compared or mixed:
+///
+/// ```compile_fail
+/// use trusted::container::container::scope;
+///
+/// enum Row {}
+/// enum Col {}
+///
+/// scope(vec![0; 4], |v| {
+///     let rows = v.range().retag::<Row>();
+///     let cols = v.range().retag::<Col>();
+///     rows.first() == cols.first();
+/// });
+/// ```
 #[derive(Debug)]
-pub struct Index<'id, Proof = NonEmpty> {
+pub struct Index<'id, Proof = NonEmpty, Tag = ()> {
     pub(crate) index: usize,
-    id: Id<'id>,
+    _id: Id<'id>,
     proof: PhantomData<Proof>,
+    tag: PhantomData<Tag>,
 }
 
-impl<'id, P> Index<'id, P> {
+/// An index that is not known to point at an element.
+/// It might be equal to the length of the container,
+/// making it an edge between two elements (or past the last one).
+pub type Edge<'id> = Index<'id, Unknown>;
+
+impl<'id, P, Tag> Index<'id, P, Tag> {
     /// Creates a new Index from `index`.
     /// This function is marked unsafe,
     /// because `index` could come from anywhere,
     /// and is therefore not known to be valid.
     #[inline(always)]
-    pub const unsafe fn new(index: usize) -> Index<'id, P> {
+    pub const unsafe fn new(index: usize) -> Index<'id, P, Tag> {
         Index {
-            id: Id::new(),
+            _id: Id::new(),
             index,
             proof: PhantomData,
+            tag: PhantomData,
         }
     }
 
@@ -33,49 +62,99 @@ impl<'id, P> Index<'id, P> {
     pub const fn integer(&self) -> usize {
         self.index
     }
+
+    /// Returns the same index, with its tag changed to `U`.
+    #[inline(always)]
+    pub const fn retag<U>(self) -> Index<'id, P, U> {
+        unsafe { Index::new(self.index) }
+    }
+
+    /// Returns the same index, with the default tag.
+    #[inline(always)]
+    pub const fn untag(self) -> Index<'id, P> {
+        self.retag()
+    }
 }
 
-impl<'id, P> Copy for Index<'id, P> {}
+impl<'id, P, Tag> Copy for Index<'id, P, Tag> {}
 
-impl<'id, P> Clone for Index<'id, P> {
+impl<'id, P, Tag> Clone for Index<'id, P, Tag> {
     #[inline]
     fn clone(&self) -> Self {
         *self
     }
 }
 
-/// Index can only be compared with other indices of the same branding
-impl<'id, P, Q> PartialEq<Index<'id, Q>> for Index<'id, P> {
+/// Index can only be compared with other indices of the same branding and tag
+impl<'id, P, Q, Tag> PartialEq<Index<'id, Q, Tag>> for Index<'id, P, Tag> {
     #[inline(always)]
-    fn eq(&self, rhs: &Index<'id, Q>) -> bool {
+    fn eq(&self, rhs: &Index<'id, Q, Tag>) -> bool {
         self.index == rhs.index
     }
 }
 
-impl<'id, P> Eq for Index<'id, P> {}
+impl<'id, P, Tag> Eq for Index<'id, P, Tag> {}
 
-impl<'id, P, Q> PartialOrd<Index<'id, Q>> for Index<'id, P> {
+impl<'id, P, Q, Tag> PartialOrd<Index<'id, Q, Tag>> for Index<'id, P, Tag> {
     #[inline(always)]
-    fn partial_cmp(&self, rhs: &Index<'id, Q>) -> Option<Ordering> {
+    fn partial_cmp(&self, rhs: &Index<'id, Q, Tag>) -> Option<Ordering> {
         Some(self.index.cmp(&rhs.index))
     }
 
     #[inline(always)]
-    fn lt(&self, rhs: &Index<'id, Q>) -> bool {
+    fn lt(&self, rhs: &Index<'id, Q, Tag>) -> bool {
         self.index < rhs.index
     }
 }
 
-impl<'id, P> Ord for Index<'id, P> {
+impl<'id, P, Tag> Ord for Index<'id, P, Tag> {
     #[inline(always)]
     fn cmp(&self, rhs: &Self) -> Ordering {
         self.index.cmp(&rhs.index)
     }
 }
 
-impl<'id, P> Hash for Index<'id, P> {
+impl<'id, P, Tag> Hash for Index<'id, P, Tag> {
     #[inline(always)]
     fn hash<H: Hasher>(&self, h: &mut H) {
         self.index.hash(h)
     }
 }
+
+/// The error returned when an integer index is out of bounds of a container.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct IndexError {
+    index: usize,
+    len: usize,
+}
+
+impl IndexError {
+    #[inline]
+    pub(crate) const fn new(index: usize, len: usize) -> IndexError {
+        IndexError { index, len }
+    }
+
+    /// Returns the index that was out of bounds.
+    #[inline]
+    pub const fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the length of the container the index was checked against.
+    #[inline]
+    pub const fn container_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl Display for IndexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "index out of bounds: the len is {} but the index is {}",
+            self.len, self.index
+        )
+    }
+}
+
+impl Error for IndexError {}
@@ -1,4 +1,6 @@
+pub mod compact;
 pub mod id;
 pub mod index;
 pub mod proof;
 pub mod range;
+pub mod unbranded;
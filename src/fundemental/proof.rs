@@ -1,5 +1,3 @@
-use std::mem;
-
 use super::{index::Index, range::Range};
 
 #[derive(Debug)]
@@ -10,6 +8,19 @@ pub enum Unknown {}
 /// Represents aa length known to be non-zero.
 pub enum NonEmpty {}
 
+/// A proof about the length of a range, rather than about its positions.
+/// Only these proofs transfer to the parts of a range
+/// that are split at an arbitrary position.
+pub trait LengthProof {}
+
+impl LengthProof for Unknown {}
+impl LengthProof for NonEmpty {}
+
+#[derive(Debug)]
+/// Represents a position known to lie on a char boundary of a string.
+/// The position might be equal to the length of the string.
+pub enum CharBoundary {}
+
 /// A trait representing the sum of proof P and Q.
 pub trait ProofAdd {
     type Sum;
@@ -20,8 +31,10 @@ impl<Q> ProofAdd for (NonEmpty, Q) {
     type Sum = NonEmpty;
 }
 
-/// Unknown + Q = Q.
-impl<Q> ProofAdd for (Unknown, Q) {
+/// Unknown + Q = Q, for any proof about the length.
+/// A position proof like `CharBoundary` does not hold for the joined range,
+/// because the start of the range comes from the Unknown range.
+impl<Q: LengthProof> ProofAdd for (Unknown, Q) {
     type Sum = Q;
 }
 
@@ -34,22 +47,22 @@ pub trait Provable {
     fn no_proof(self) -> Self::WithoutProof;
 }
 
-impl<'id, P> Provable for Index<'id, P> {
+impl<'id, P, Tag> Provable for Index<'id, P, Tag> {
     type Proof = P;
-    type WithoutProof = Index<'id, Unknown>;
+    type WithoutProof = Index<'id, Unknown, Tag>;
 
     #[inline(always)]
     fn no_proof(self) -> Self::WithoutProof {
-        unsafe { mem::transmute(self) }
+        unsafe { Index::new(self.integer()) }
     }
 }
 
-impl<'id, P> Provable for Range<'id, P> {
+impl<'id, P, Tag> Provable for Range<'id, P, Tag> {
     type Proof = P;
-    type WithoutProof = Range<'id, Unknown>;
+    type WithoutProof = Range<'id, Unknown, Tag>;
 
     #[inline(always)]
     fn no_proof(self) -> Self::WithoutProof {
-        unsafe { mem::transmute(self) }
+        unsafe { Range::from_any(self.start(), self.end()) }
     }
 }
@@ -1,7 +1,7 @@
 use super::{
     id::Id,
     index::Index,
-    proof::{NonEmpty, ProofAdd, Unknown},
+    proof::{LengthProof, NonEmpty, ProofAdd, Unknown},
 };
 
 use std::{
@@ -14,12 +14,15 @@ use std::{
 /// which indicates whether the range is know to be non-empty (NonEmpty),
 /// or not (Unknown).
 /// A NonEmpty range has a length of 1 or more.
+/// Like `Index`, a range carries an optional `Tag`,
+/// which is transferred to the indices it yields.
 #[derive(Debug)]
-pub struct Range<'id, Proof = Unknown> {
+pub struct Range<'id, Proof = Unknown, Tag = ()> {
     _id: Id<'id>,
     start: usize,
     end: usize,
     proof: PhantomData<Proof>,
+    tag: PhantomData<Tag>,
 }
 
 impl<'id> Range<'id> {
@@ -34,6 +37,7 @@ impl<'id> Range<'id> {
             start,
             end,
             proof: PhantomData,
+            tag: PhantomData,
         }
     }
 }
@@ -50,55 +54,57 @@ impl<'id> Range<'id, NonEmpty> {
             start,
             end,
             proof: PhantomData,
+            tag: PhantomData,
         }
     }
 }
 
-impl<'id, P> Range<'id, P> {
+impl<'id, P, Tag> Range<'id, P, Tag> {
     /// Creates a new range from `start` and `end`.
     /// This function is marked unsafe,
     /// because it can not be proved `start` and `end` are a valid
     /// range of the container.
     #[inline]
-    pub const unsafe fn from_any(start: usize, end: usize) -> Range<'id, P> {
+    pub const unsafe fn from_any(start: usize, end: usize) -> Range<'id, P, Tag> {
         Range {
             _id: Id::new(),
             start,
             end,
             proof: PhantomData,
+            tag: PhantomData,
         }
     }
 }
 
-impl<'id, P> Copy for Range<'id, P> {}
+impl<'id, P, Tag> Copy for Range<'id, P, Tag> {}
 
-impl<'id, P> Clone for Range<'id, P> {
+impl<'id, P, Tag> Clone for Range<'id, P, Tag> {
     fn clone(&self) -> Self {
         *self
     }
 }
 
-impl<'id, P, Q> PartialEq<Range<'id, Q>> for Range<'id, P> {
-    fn eq(&self, other: &Range<'id, Q>) -> bool {
+impl<'id, P, Q, Tag> PartialEq<Range<'id, Q, Tag>> for Range<'id, P, Tag> {
+    fn eq(&self, other: &Range<'id, Q, Tag>) -> bool {
         self.start == other.start && self.end == other.end
     }
 }
 
-impl<'id, P> Eq for Range<'id, P> {}
+impl<'id, P, Tag> Eq for Range<'id, P, Tag> {}
 
-impl<'id, P> Hash for Range<'id, P> {
+impl<'id, P, Tag> Hash for Range<'id, P, Tag> {
     fn hash<H: Hasher>(&self, h: &mut H) {
         self.start.hash(h);
         self.end.hash(h);
     }
 }
 
-impl<'id, P> Range<'id, P> {
+impl<'id, P, Tag> Range<'id, P, Tag> {
     /// Attemts to create a NonEmpty range, returning Some on success, None on failure.
     #[inline]
-    pub fn nonempty(&self) -> Option<Range<'id, NonEmpty>> {
+    pub fn nonempty(&self) -> Option<Range<'id, NonEmpty, Tag>> {
         if !self.is_empty() {
-            unsafe { Some(std::mem::transmute(*self)) }
+            unsafe { Some(self.nonempty_unchecked()) }
         } else {
             None
         }
@@ -132,15 +138,26 @@ impl<'id, P> Range<'id, P> {
     /// upper middle index landing in the latter half.
     /// Proof of length `P` transfers to the latter half.
     #[inline]
-    pub const fn split_in_half(&self) -> (Range<'id>, Range<'id, P>) {
+    pub const fn split_in_half(&self) -> (Range<'id, Unknown, Tag>, Range<'id, P, Tag>)
+    where
+        P: LengthProof,
+    {
         let mid = (self.end - self.start) / 2 + self.start;
 
-        unsafe { (Range::from(self.start, mid), Range::from_any(mid, self.end)) }
+        unsafe {
+            (
+                Range::from_any(self.start, mid),
+                Range::from_any(mid, self.end),
+            )
+        }
     }
 
     /// Split the range at `index`. if past the end, return false and clamp to the end.
     #[inline]
-    pub fn split_at(&self, index: usize) -> (Range<'id>, Range<'id>, bool) {
+    pub fn split_at(
+        &self,
+        index: usize,
+    ) -> (Range<'id, Unknown, Tag>, Range<'id, Unknown, Tag>, bool) {
         let mid = if index > self.len() {
             self.end
         } else {
@@ -149,8 +166,8 @@ impl<'id, P> Range<'id, P> {
 
         unsafe {
             (
-                Range::from(self.start, mid),
-                Range::from(mid, self.end),
+                Range::from_any(self.start, mid),
+                Range::from_any(mid, self.end),
                 index <= self.len(),
             )
         }
@@ -158,7 +175,10 @@ impl<'id, P> Range<'id, P> {
 
     /// Returns Some if `index` is contained within the range.
     #[inline]
-    pub fn contains(&self, index: usize) -> Option<Index<'id, P>> {
+    pub fn contains(&self, index: usize) -> Option<Index<'id, P, Tag>>
+    where
+        P: LengthProof,
+    {
         unsafe {
             if index >= self.start && index < self.end {
                 Some(Index::new(index))
@@ -170,11 +190,25 @@ impl<'id, P> Range<'id, P> {
 
     /// Join together two adjacent ranges (they must be exactly touching, and
     /// in left to right order).
+    ///
+    /// A range starting at an arbitrary position can not become a `CharBoundary` range:
+    ///
+    /// ```compile_fail
+    /// use trusted::container::container::scope;
+    ///
+    /// scope("éa", |s| {
+    ///     let (a, _) = s.char_indices().nth(1).unwrap();
+    ///     let (_, tail) = s.split_str_at(a);
+    ///     let (_, rest, _) = s.range().split_at(1);
+    ///     let (middle, _, _) = rest.split_at(1);
+    ///     let _ = s.substr(middle.join(tail).unwrap());
+    /// });
+    /// ```
     #[inline]
     pub const fn join<Q>(
         &self,
-        other: Range<'id, Q>,
-    ) -> Option<Range<'id, <(P, Q) as ProofAdd>::Sum>>
+        other: Range<'id, Q, Tag>,
+    ) -> Option<Range<'id, <(P, Q) as ProofAdd>::Sum, Tag>>
     where
         (P, Q): ProofAdd,
     {
@@ -191,25 +225,28 @@ impl<'id, P> Range<'id, P> {
     }
 }
 
-impl<'id, P> Range<'id, P> {
+impl<'id, P, Tag> Range<'id, P, Tag> {
     /// Creates an unchecked NonEmpty range.
     /// # Unsafe
     /// This function is marked unsafe,
     /// because it's not checked whether the range is truely NonEmpty.
     #[inline]
-    pub const unsafe fn nonempty_unchecked(&self) -> Range<'id, NonEmpty> {
+    pub const unsafe fn nonempty_unchecked(&self) -> Range<'id, NonEmpty, Tag> {
         Range::from_any(self.start, self.end)
     }
 
     /// Returns the first Index of the range.
     #[inline]
-    pub const fn first(&self) -> Index<'id, P> {
+    pub const fn first(&self) -> Index<'id, P, Tag> {
         unsafe { Index::new(self.start) }
     }
 
     /// Returns the middle Index of the range.
     #[inline]
-    pub const fn upper_middle(&self) -> Index<'id, P> {
+    pub const fn upper_middle(&self) -> Index<'id, P, Tag>
+    where
+        P: LengthProof,
+    {
         let mid = self.len() / 2 + self.start;
 
         unsafe { Index::new(mid) }
@@ -217,17 +254,35 @@ impl<'id, P> Range<'id, P> {
 
     /// Split the range at `index`. Proof of length `P` transfers to the latter end.
     #[inline]
-    pub const fn split_index(&self, index: Index<'id>) -> (Range<'id>, Range<'id, P>) {
+    pub const fn split_index(
+        &self,
+        index: Index<'id, NonEmpty, Tag>,
+    ) -> (Range<'id, Unknown, Tag>, Range<'id, P, Tag>)
+    where
+        P: LengthProof,
+    {
         unsafe {
             (
-                Range::from(self.start, index.integer()),
+                Range::from_any(self.start, index.integer()),
                 Range::from_any(index.integer(), self.end),
             )
         }
     }
+
+    /// Returns the same range, with its tag changed to `U`.
+    #[inline]
+    pub const fn retag<U>(self) -> Range<'id, P, U> {
+        unsafe { Range::from_any(self.start, self.end) }
+    }
+
+    /// Returns the same range, with the default tag.
+    #[inline]
+    pub const fn untag(self) -> Range<'id, P> {
+        self.retag()
+    }
 }
 
-impl<'id> Range<'id, NonEmpty> {
+impl<'id, Tag> Range<'id, NonEmpty, Tag> {
     /// Splits the range at `index`.
     /// # Unsafe
     /// This function is marked unsafe,
@@ -236,32 +291,32 @@ impl<'id> Range<'id, NonEmpty> {
     #[inline]
     pub const unsafe fn unsafe_split_index(
         &self,
-        index: Index<'id>,
-    ) -> (Range<'id, NonEmpty>, Range<'id, NonEmpty>) {
+        index: Index<'id, NonEmpty, Tag>,
+    ) -> (Range<'id, NonEmpty, Tag>, Range<'id, NonEmpty, Tag>) {
         (
-            Range::from_ne(self.start, index.integer()),
-            Range::from_ne(index.integer(), self.end),
+            Range::from_any(self.start, index.integer()),
+            Range::from_any(index.integer(), self.end),
         )
     }
 
     /// Returns the last Index of the range.
     #[inline]
-    pub const fn last(&self) -> Index<'id> {
+    pub const fn last(&self) -> Index<'id, NonEmpty, Tag> {
         unsafe { Index::new(self.end - 1) }
     }
 
     /// Returns a new range,
     /// containing indices from *this* range's second index to *this* range's end index.
     #[inline]
-    pub const fn tail(self) -> Range<'id> {
-        unsafe { Range::from(self.start + 1, self.end) }
+    pub const fn tail(self) -> Range<'id, Unknown, Tag> {
+        unsafe { Range::from_any(self.start + 1, self.end) }
     }
 
     /// Returns a new range,
     /// containing indices from *this* range's first index, to *this* range's second last index.
     #[inline]
-    pub const fn head(self) -> Range<'id> {
-        unsafe { Range::from(self.start, self.end - 1) }
+    pub const fn head(self) -> Range<'id, Unknown, Tag> {
+        unsafe { Range::from_any(self.start, self.end - 1) }
     }
 
     /// Advance's the range backwards.
@@ -294,9 +349,9 @@ impl<'id> Range<'id, NonEmpty> {
     }
 }
 
-impl<'id, P> IntoIterator for Range<'id, P> {
-    type Item = Index<'id>;
-    type IntoIter = RangeIter<'id>;
+impl<'id, P, Tag> IntoIterator for Range<'id, P, Tag> {
+    type Item = Index<'id, NonEmpty, Tag>;
+    type IntoIter = RangeIter<'id, Tag>;
 
     #[inline(always)]
     fn into_iter(self) -> Self::IntoIter {
@@ -304,20 +359,29 @@ impl<'id, P> IntoIterator for Range<'id, P> {
             _id: self._id,
             start: self.start,
             end: self.end,
+            tag: PhantomData,
         }
     }
 }
 
 /// An Iterator between the range `start..end`.
-#[derive(Copy, Clone)]
-pub struct RangeIter<'id> {
+pub struct RangeIter<'id, Tag = ()> {
     _id: Id<'id>,
     start: usize,
     end: usize,
+    tag: PhantomData<Tag>,
+}
+
+impl<'id, Tag> Copy for RangeIter<'id, Tag> {}
+
+impl<'id, Tag> Clone for RangeIter<'id, Tag> {
+    fn clone(&self) -> Self {
+        *self
+    }
 }
 
-impl<'id> Iterator for RangeIter<'id> {
-    type Item = Index<'id>;
+impl<'id, Tag> Iterator for RangeIter<'id, Tag> {
+    type Item = Index<'id, NonEmpty, Tag>;
 
     #[inline(always)]
     fn next(&mut self) -> Option<Self::Item> {
@@ -331,7 +395,7 @@ impl<'id> Iterator for RangeIter<'id> {
     }
 }
 
-impl<'id> DoubleEndedIterator for RangeIter<'id> {
+impl<'id, Tag> DoubleEndedIterator for RangeIter<'id, Tag> {
     #[inline(always)]
     fn next_back(&mut self) -> Option<Self::Item> {
         if self.start < self.end {
@@ -344,7 +408,7 @@ impl<'id> DoubleEndedIterator for RangeIter<'id> {
     }
 }
 
-impl<'id> std::iter::ExactSizeIterator for RangeIter<'id> {
+impl<'id, Tag> std::iter::ExactSizeIterator for RangeIter<'id, Tag> {
     fn len(&self) -> usize {
         self.end - self.start
     }
@@ -0,0 +1,70 @@
+use super::{index::Index, range::Range};
+
+/// An index that is not tied to any container.
+///
+/// Unlike `Index`, an unbranded index can be stored freely,
+/// outside of the scope it was created in.
+/// It has to be vetted again with `Container::revet` before it can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UnbrandedIndex {
+    index: usize,
+}
+
+impl UnbrandedIndex {
+    /// Creates a new unbranded index from `index`.
+    #[inline(always)]
+    pub const fn new(index: usize) -> UnbrandedIndex {
+        UnbrandedIndex { index }
+    }
+
+    /// Return the index as an integer offset from the start of the container.
+    #[inline(always)]
+    pub const fn integer(&self) -> usize {
+        self.index
+    }
+}
+
+impl<'id, P, Tag> From<Index<'id, P, Tag>> for UnbrandedIndex {
+    #[inline(always)]
+    fn from(index: Index<'id, P, Tag>) -> UnbrandedIndex {
+        UnbrandedIndex::new(index.integer())
+    }
+}
+
+/// A range that is not tied to any container.
+///
+/// Unlike `Range`, an unbranded range can be stored freely,
+/// outside of the scope it was created in.
+/// It has to be vetted again with `Container::revet_range` before it can be used.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct UnbrandedRange {
+    start: usize,
+    end: usize,
+}
+
+impl UnbrandedRange {
+    /// Creates a new unbranded range from `start` and `end`.
+    #[inline(always)]
+    pub const fn new(start: usize, end: usize) -> UnbrandedRange {
+        UnbrandedRange { start, end }
+    }
+
+    /// Returns the start index of the range.
+    #[inline(always)]
+    pub const fn start(&self) -> usize {
+        self.start
+    }
+
+    /// Returns the end index of the range.
+    #[inline(always)]
+    pub const fn end(&self) -> usize {
+        self.end
+    }
+}
+
+impl<'id, P, Tag> From<Range<'id, P, Tag>> for UnbrandedRange {
+    #[inline(always)]
+    fn from(range: Range<'id, P, Tag>) -> UnbrandedRange {
+        UnbrandedRange::new(range.start(), range.end())
+    }
+}
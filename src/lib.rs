@@ -1,3 +1,5 @@
+#![allow(clippy::missing_safety_doc, clippy::module_inception)]
+
 pub mod algorithms;
 pub mod container;
 pub mod fundemental;